@@ -2,6 +2,7 @@
 mod tests {
     use struct_path::*;
 
+    #[allow(dead_code)]
     pub struct TestStructParent {
         pub value_str: String,
         pub value_num: u64,
@@ -9,6 +10,8 @@ mod tests {
         pub opt_value_child: Option<TestStructChild>,
     }
 
+    #[allow(dead_code)]
+    #[derive(serde::Serialize)]
     pub struct TestStructChild {
         pub child_value_str: String,
         pub child_value_num: u64,
@@ -27,17 +30,1675 @@ mod tests {
 
         assert_eq!(test_another_delim, "valueChild/childValueStr");
 
+        let test_pascal_case =
+            path!(TestStructParent::value_child.child_value_str; case = "pascal");
+        assert_eq!(test_pascal_case, "ValueChild.ChildValueStr");
+
+        let test_kebab_case =
+            path!(TestStructParent::value_child.child_value_str; delim = "/", case = "kebab");
+        assert_eq!(test_kebab_case, "value-child/child-value-str");
+
+        let test_screaming_case =
+            path!(TestStructParent::value_child.child_value_str; delim = "__", case = "screaming");
+        assert_eq!(test_screaming_case, "VALUE_CHILD__CHILD_VALUE_STR");
+
+        let test_upper_snake_case =
+            path!(TestStructParent::value_child.child_value_str; case = "upper_snake");
+        assert_eq!(test_upper_snake_case, "VALUE_CHILD.CHILD_VALUE_STR");
+
+        let test_typed_case =
+            path!(TestStructParent::value_child.child_value_str; case = Case::Camel, delim = "/");
+        assert_eq!(test_typed_case, "valueChild/childValueStr");
+
+        let test_typed_paths =
+            paths!(TestStructParent::{ value_str, value_num }; case = Case::UpperSnake);
+        assert_eq!(test_typed_paths, ["VALUE_STR", "VALUE_NUM"]);
+
+        let test_kebab_paths = paths!(TestStructParent::{ value_str, opt_value_child~child_value_num }; case = "kebab");
+        assert_eq!(
+            test_kebab_paths,
+            ["value-str", "opt-value-child.child-value-num"]
+        );
+
         let test_full_path = path!(crate::tests::TestStructParent::value_str);
         assert_eq!(test_full_path, "value_str");
 
-        let test_mixed_path = path!(
-            TestStructParent::value_str,
-            TestStructChild::child_value_str
+        let test_mixed_path = path!(
+            TestStructParent::value_str,
+            TestStructChild::child_value_str
+        );
+        assert_eq!(test_mixed_path, "value_str.child_value_str");
+
+        let test_opt_child = path!(TestStructParent::opt_value_child~child_value_str);
+        assert_eq!(test_opt_child, "opt_value_child.child_value_str");
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructAbbrev {
+        pub identifier: u64,
+        pub user_identifier: u64,
+        pub configuration: TestStructChild,
+    }
+
+    #[test]
+    fn struct_path_abbrev() {
+        let test_abbrev = path!(TestStructAbbrev::configuration.child_value_str; abbrev = "identifier=id,configuration=config");
+        assert_eq!(test_abbrev, "config.child_value_str");
+
+        let test_abbrev_case = paths!(TestStructAbbrev::{ identifier, user_identifier }; abbrev = "identifier=id, user_identifier=user_id", case = "camel");
+        assert_eq!(test_abbrev_case, ["id", "userId"]);
+
+        let test_abbrev_whole_segments =
+            paths!(TestStructAbbrev::{ identifier, user_identifier }; abbrev = "identifier=id");
+        assert_eq!(test_abbrev_whole_segments, ["id", "user_identifier"]);
+    }
+
+    #[test]
+    fn struct_path_segment_pattern() {
+        let test_pattern = path!(TestStructParent::value_child.child_value_str; segment_pattern = "[a-z][a-z0-9_]*");
+        assert_eq!(test_pattern, "value_child.child_value_str");
+
+        let test_pattern_case = paths!(TestStructParent::{ value_str, value_num }; case = "camel", segment_pattern = "value[A-Z][^_]+");
+        assert_eq!(test_pattern_case, ["valueStr", "valueNum"]);
+
+        let test_pattern_quantifiers =
+            path!(TestStructAddress::line_2; segment_pattern = "l.n?e_[0-9]?x*");
+        assert_eq!(test_pattern_quantifiers, "line_2");
+
+        let test_pattern_literal = path!(TestStructParent::"items".value_child.child_value_str; segment_pattern = "[a-z][a-z0-9_]*");
+        assert_eq!(test_pattern_literal, "items.value_child.child_value_str");
+    }
+
+    #[allow(dead_code)]
+    #[derive(StructPath)]
+    pub struct TestStructDerived {
+        pub value_str: String,
+        pub value_map: std::collections::HashMap<String, u64>,
+        pub r#type: u64,
+    }
+
+    #[test]
+    fn struct_field_of() {
+        let test_field = field_of!(TestStructDerived::value_str);
+        assert_eq!(test_field, TestStructDerivedField::ValueStr);
+        assert_eq!(test_field.path(), path!(TestStructDerived::value_str));
+
+        let test_full_path_field = field_of!(crate::tests::TestStructDerived::value_map);
+        assert_eq!(test_full_path_field.path(), "value_map");
+
+        assert_eq!(field_of!(TestStructDerived::r#type).path(), "type");
+    }
+
+    #[test]
+    fn struct_path_with_index() {
+        assert_eq!(
+            path_with_index!(TestStructDerived::value_str),
+            ("value_str", 0)
+        );
+        assert_eq!(
+            path_with_index!(TestStructDerived::value_map; case = "camel"),
+            ("valueMap", 1)
+        );
+        assert_eq!(TestStructDerivedField::Type.index(), 2);
+    }
+
+    #[allow(dead_code)]
+    #[derive(StructPath)]
+    pub struct TestStructJsonTypes<'a, T: Clone>
+    where
+        T: Default,
+    {
+        pub value_str: &'a str,
+        pub value_num: u64,
+        pub value_flag: bool,
+        pub value_vec: Vec<T>,
+        pub value_child: TestStructChild,
+        pub opt_value_num: Option<f64>,
+        pub value_generic: T,
+    }
+
+    #[test]
+    fn struct_derived_json_types() {
+        assert_eq!(
+            TestStructJsonTypes::<'static, u64>::JSON_TYPES,
+            &[
+                ("value_str", "string"),
+                ("value_num", "number"),
+                ("value_flag", "boolean"),
+                ("value_vec", "array"),
+                ("value_child", "object"),
+                ("opt_value_num", "number"),
+                ("value_generic", "object"),
+            ]
+        );
+        assert_eq!(TestStructDerived::JSON_TYPES[1], ("value_map", "object"));
+    }
+
+    #[allow(dead_code)]
+    #[derive(Default, StructPath)]
+    #[struct_path(unwrap_options)]
+    pub struct TestStructBuilder {
+        pub opt_value_str: Option<String>,
+        pub opt_value_child: Option<TestStructChild>,
+        pub value_num: u64,
+    }
+
+    #[test]
+    fn struct_derived_unwrap_options() {
+        assert_eq!(
+            path!(TestStructBuilderUnwrapped::opt_value_child.child_value_str),
+            "opt_value_child.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructBuilderUnwrapped::opt_value_str),
+            path!(TestStructBuilder::opt_value_str)
+        );
+        assert_eq!(
+            path!(TestStructBuilderUnwrapped::value_num),
+            path!(TestStructBuilder::value_num)
+        );
+    }
+
+    #[derive(StructPath)]
+    #[struct_path(match_paths)]
+    pub struct TestStructVisited {
+        pub value_str: String,
+        pub value_num: u64,
+        pub opt_value_flag: Option<bool>,
+    }
+
+    #[test]
+    fn struct_derived_match_paths() {
+        let test_struct = TestStructVisited {
+            value_str: "test-str".to_string(),
+            value_num: 42,
+            opt_value_flag: Some(true),
+        };
+
+        let test_visited: Vec<String> = ["value_num", "opt_value_flag", "value_str"]
+            .iter()
+            .map(|test_path| {
+                format!(
+                    "{:?}",
+                    match_paths!(&test_struct: &TestStructVisited, test_path).unwrap()
+                )
+            })
+            .collect();
+        assert_eq!(test_visited, ["42", "Some(true)", "\"test-str\""]);
+
+        let test_unknown_path = "unknown_value";
+        assert!(match_paths!(&test_struct: &TestStructVisited, test_unknown_path).is_none());
+    }
+
+    #[test]
+    fn struct_derived_nullable() {
+        assert_eq!(
+            TestStructJsonTypes::<'static, u64>::NULLABLE,
+            &[
+                ("value_str", false),
+                ("value_num", false),
+                ("value_flag", false),
+                ("value_vec", false),
+                ("value_child", false),
+                ("opt_value_num", true),
+                ("value_generic", false),
+            ]
+        );
+    }
+
+    #[allow(dead_code)]
+    #[derive(StructPath)]
+    pub struct TestStructFixedWidth {
+        #[struct_path(width = 8)]
+        pub account_id: String,
+        #[allow(unused)]
+        #[struct_path(width = 12)]
+        pub amount: u64,
+        pub comment: String,
+    }
+
+    #[test]
+    fn struct_derived_widths() {
+        assert_eq!(
+            TestStructFixedWidth::WIDTHS,
+            &[("account_id", 8), ("amount", 12)]
+        );
+        assert!(TestStructVisited::WIDTHS.is_empty());
+    }
+
+    mod test_serde_as_string {
+        pub fn serialize<S: serde::Serializer>(
+            value: &u64,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&value.to_string())
+        }
+    }
+
+    #[allow(dead_code)]
+    #[derive(StructPath, serde::Serialize)]
+    pub struct TestStructCustomSerde {
+        #[serde(with = "test_serde_as_string")]
+        pub created_at: u64,
+        #[serde(rename = "userId", serialize_with = "test_serde_as_string::serialize")]
+        pub user_id: u64,
+        pub comment: String,
+    }
+
+    #[test]
+    fn struct_derived_custom_serde() {
+        assert_eq!(
+            TestStructCustomSerde::CUSTOM_SERDE,
+            &["created_at", "user_id"]
+        );
+        assert!(TestStructVisited::CUSTOM_SERDE.is_empty());
+    }
+
+    #[test]
+    fn struct_path_keyed() {
+        let test_keyed = path_keyed!(TestStructParent::value_child.child_value_str);
+        assert_eq!(
+            test_keyed,
+            (
+                std::any::TypeId::of::<TestStructParent>(),
+                "value_child.child_value_str"
+            )
+        );
+
+        let test_keyed_mixed = path_keyed!(
+            TestStructParent::value_child,
+            TestStructChild::child_value_str; case = "camel"
+        );
+        assert_eq!(
+            test_keyed_mixed.0,
+            std::any::TypeId::of::<TestStructParent>()
+        );
+        assert_eq!(test_keyed_mixed.1, "valueChild.childValueStr");
+        assert_ne!(
+            path_keyed!(TestStructChild::child_value_str).0,
+            path_keyed!(TestStructParent::value_str).0
+        );
+    }
+
+    #[test]
+    fn struct_path_arc() {
+        let test_arc: std::sync::Arc<str> =
+            path_arc!(TestStructParent::value_child.child_value_str);
+        assert_eq!(&*test_arc, "value_child.child_value_str");
+
+        let test_arc_clone = test_arc.clone();
+        assert!(std::sync::Arc::ptr_eq(&test_arc, &test_arc_clone));
+
+        let test_arc_options =
+            path_arc!(TestStructParent::opt_value_child~child_value_str; case = "camel");
+        assert_eq!(&*test_arc_options, "optValueChild.childValueStr");
+    }
+
+    #[test]
+    fn struct_path_accessor() {
+        let mut test_struct = TestStructParent {
+            value_str: "test-str".to_string(),
+            value_num: 42,
+            value_child: TestStructChild {
+                child_value_str: "test-child-str".to_string(),
+                child_value_num: 24,
+            },
+            opt_value_child: None,
+        };
+
+        let test_accessor = path_accessor!(TestStructParent::value_num);
+        assert_eq!(test_accessor.path, "value_num");
+        assert_eq!(*(test_accessor.get)(&test_struct), 42);
+        (test_accessor.set)(&mut test_struct, 43);
+        assert_eq!(test_struct.value_num, 43);
+
+        let test_nested_accessor =
+            path_accessor!(TestStructParent::value_child.child_value_str; case = "camel");
+        assert_eq!(test_nested_accessor.path, "valueChild.childValueStr");
+        assert_eq!((test_nested_accessor.get)(&test_struct), "test-child-str");
+        (test_nested_accessor.set)(&mut test_struct, "updated-child-str".to_string());
+        assert_eq!(test_struct.value_child.child_value_str, "updated-child-str");
+
+        let test_escaped_accessor =
+            path_accessor!(TestStructParent::value_num; prefix = "\"quoted\\");
+        assert_eq!(test_escaped_accessor.path, "\"quoted\\.value_num");
+    }
+
+    #[allow(dead_code)]
+    #[derive(PartialEq, Eq, Hash)]
+    pub enum TestKind {
+        Primary,
+        Secondary,
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructByKind {
+        pub by_kind: std::collections::HashMap<TestKind, TestStructChild>,
+    }
+
+    #[test]
+    fn struct_path_enum_keys() {
+        assert_eq!(
+            path!(TestStructByKind::by_kind[TestKind::Primary].child_value_str),
+            "by_kind.Primary.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructByKind::by_kind[self::TestKind::Secondary].child_value_num; case = "camel"),
+            "byKind.Secondary.childValueNum"
+        );
+    }
+
+    #[test]
+    fn struct_assert_path_eq_macro() {
+        assert_path_eq_macro!(
+            path!(TestStructParent::value_child.child_value_str),
+            path!(
+                TestStructParent::value_child,
+                TestStructChild::child_value_str
+            )
+        );
+        assert_path_eq_macro!(path!(TestStructAddress::line_2; case = "camel"), "line2");
+        assert_path_eq_macro!(
+            path!(TestStructSettings::by_name{*}child_value_str),
+            "by_name.*.child_value_str"
+        );
+    }
+
+    fn generic_value_str_path<T>(_value: &T) -> &'static str {
+        path!(T::value_str; check = false)
+    }
+
+    #[test]
+    fn struct_path_unchecked() {
+        let test_struct = TestStructChild {
+            child_value_str: "test-child-str".to_string(),
+            child_value_num: 24,
+        };
+        assert_eq!(generic_value_str_path(&test_struct), "value_str");
+        assert_eq!(
+            paths!(TestStructParent::{ value_str, unknown_value }; check = false, case = "camel"),
+            ["valueStr", "unknownValue"]
+        );
+    }
+
+    #[test]
+    fn struct_path_dyn_delim() {
+        let test_delims = ["/", "::"];
+        let test_delim = test_delims[0];
+
+        assert_eq!(
+            path_dyn_delim!(TestStructParent::value_child.child_value_str, test_delim),
+            "value_child/child_value_str"
+        );
+        assert_eq!(
+            path_dyn_delim!(TestStructParent::value_child.child_value_str, test_delims[1]; case = "camel"),
+            "valueChild::childValueStr"
+        );
+        assert_eq!(
+            path_dyn_delim!(
+                TestStructParent::value_child,
+                TestStructChild::child_value_num,
+                "-"
+            ),
+            "value_child-child_value_num"
+        );
+        assert_eq!(
+            path_dyn_delim!(TestStructOrder::items[$].child_value_str, test_delim),
+            "items/$/child_value_str"
+        );
+        assert_eq!(
+            path_dyn_delim!(TestStructParent::value_child.child_value_str, test_delim; prefix = "data", include_root = true, suffix = "raw"),
+            path!(TestStructParent::value_child.child_value_str; prefix = "data", include_root = true, suffix = "raw", delim = "/")
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn struct_path_setter_json() {
+        let mut test_json = serde_json::json!({ "value_str": "test-str" });
+
+        let test_setter = path_setter_json!(TestStructParent::value_child.child_value_str);
+        test_setter(&mut test_json, serde_json::json!("test-child-str"));
+        path_setter_json!(TestStructParent::value_child.child_value_num)(
+            &mut test_json,
+            serde_json::json!(24),
+        );
+        assert_eq!(
+            test_json,
+            serde_json::json!({
+                "value_str": "test-str",
+                "value_child": {
+                    "child_value_str": "test-child-str",
+                    "child_value_num": 24
+                }
+            })
+        );
+
+        path_setter_json!(TestStructParent::value_str.len; check = false)(
+            &mut test_json,
+            serde_json::json!(8),
+        );
+        assert_eq!(test_json["value_str"], serde_json::json!({ "len": 8 }));
+
+        let mut test_camel_json = serde_json::Value::Null;
+        path_setter_json!(TestStructParent::value_child.child_value_str; case = "camel")(
+            &mut test_camel_json,
+            serde_json::json!("test-child-str"),
+        );
+        assert_eq!(
+            test_camel_json,
+            serde_json::json!({ "valueChild": { "childValueStr": "test-child-str" } })
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructPrivate {
+        pub _internal: String,
+        pub __private_value: u64,
+    }
+
+    #[test]
+    fn struct_path_preserve_leading_underscore() {
+        assert_eq!(
+            path!(TestStructPrivate::_internal; case = "camel"),
+            "internal"
+        );
+        assert_eq!(
+            path!(TestStructPrivate::_internal; case = "camel", preserve_leading_underscore = true),
+            "_internal"
+        );
+        assert_eq!(
+            paths!(TestStructPrivate::{ _internal, __private_value }; case = "pascal", preserve_leading_underscore = true),
+            ["_Internal", "__PrivateValue"]
+        );
+    }
+
+    #[test]
+    fn struct_relative_path() {
+        assert_eq!(
+            relative_path!(
+                TestStructParent::value_child.child_value_str,
+                TestStructParent::value_child
+            ),
+            "child_value_str"
+        );
+        assert_eq!(
+            relative_path!(
+                crate::tests::TestStructOrder::opt_order~items[..2].child_value_num,
+                crate::tests::TestStructOrder::opt_order; case = "camel"
+            ),
+            "items[..2].childValueNum"
+        );
+    }
+
+    #[test]
+    fn struct_path_on_empty_segment() {
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; abbrev = "value_child=", on_empty_segment = "skip"),
+            "child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; abbrev = "value_child=", on_empty_segment = "keep"),
+            ".child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; on_empty_segment = "error"),
+            "value_child.child_value_str"
+        );
+    }
+
+    #[allow(dead_code, non_snake_case)]
+    pub struct TestStructBindings {
+        pub someField: String,
+        pub value_num: u64,
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructNormalized {
+        pub some_field: String,
+    }
+
+    #[test]
+    fn struct_path_snake_case() {
+        let test_snake = paths!(TestStructBindings::{ someField, value_num }; case = "snake");
+        assert_eq!(test_snake, ["some_field", "value_num"]);
+
+        assert_eq!(
+            path!(TestStructBindings::someField; case = "snake"),
+            path!(TestStructNormalized::some_field)
+        );
+        assert_eq!(
+            path!(TestStructNormalized::some_field; case = "camel"),
+            path!(TestStructBindings::someField)
+        );
+    }
+
+    #[test]
+    fn struct_path_exact_depth() {
+        assert_eq!(
+            path_exact_depth!(TestStructParent::value_child.child_value_str, 2),
+            "value_child.child_value_str"
+        );
+        assert_eq!(
+            path_exact_depth!(TestStructParent::value_child, TestStructChild::child_value_str, 2; case = "camel"),
+            "valueChild.childValueStr"
+        );
+        assert_eq!(
+            path_exact_depth!(TestStructParent::value_str, 1),
+            "value_str"
+        );
+        assert_eq!(
+            path_exact_depth!(TestStructOrder::items[$].child_value_str, 3),
+            "items.$.child_value_str"
+        );
+    }
+
+    #[test]
+    fn struct_path_append() {
+        let test_literal_base: &str =
+            path_append!("orders", TestStructParent::value_child.child_value_str);
+        assert_eq!(test_literal_base, "orders.value_child.child_value_str");
+
+        let test_bases = ["orders".to_string(), "archive".to_string()];
+        let test_expr_base: String =
+            path_append!(test_bases[1], TestStructParent::value_str; delim = "/", case = "camel");
+        assert_eq!(test_expr_base, "archive/valueStr");
+
+        let test_nested_base: String = path_append!(
+            path_append!("orders", TestStructParent::value_child).to_uppercase(),
+            TestStructChild::child_value_num
+        );
+        assert_eq!(test_nested_base, "ORDERS.VALUE_CHILD.child_value_num");
+
+        let test_braced_base: String = path_append!(
+            test_bases[0],
+            TestStructSettings::by_name."{id}".child_value_str
+        );
+        assert_eq!(test_braced_base, "orders.by_name.{id}.child_value_str");
+    }
+
+    #[test]
+    fn struct_path_prefix() {
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; prefix = "metadata"),
+            "metadata.value_child.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; prefix = "meta_data", delim = "/", case = "camel"),
+            "meta_data/valueChild/childValueStr"
+        );
+        assert_eq!(
+            paths!(TestStructParent::{ value_str, value_num }; prefix = "metadata"),
+            ["metadata.value_str", "metadata.value_num"]
+        );
+    }
+
+    #[allow(dead_code, non_snake_case)]
+    pub struct TestStructRaw {
+        pub My_Field: TestStructChild,
+        pub HTTPStatus: u16,
+    }
+
+    #[test]
+    fn struct_path_lower_raw_transform() {
+        assert_eq!(
+            path!(TestStructRaw::My_Field; transform = "lower-raw"),
+            "my_field"
+        );
+        assert_eq!(
+            paths!(TestStructRaw::{ My_Field.child_value_str, HTTPStatus }; transform = "lower-raw", delim = "/"),
+            ["my_field/child_value_str", "httpstatus"]
+        );
+    }
+
+    #[test]
+    fn struct_path_suffix() {
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; suffix = "$"),
+            "value_child.child_value_str.$"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; suffix = "$", delim = "/"),
+            "value_child/child_value_str/$"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; prefix = "x", suffix = "$", case = "camel"),
+            "x.valueChild.childValueStr.$"
+        );
+    }
+
+    #[test]
+    fn struct_path_length() {
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; length = true),
+            "value_child.child_value_str.length"
+        );
+        assert_eq!(
+            path!(TestStructParent::opt_value_child~child_value_str; length = "maxLength", case = "camel"),
+            "optValueChild.childValueStr.maxLength"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_child, TestStructChild::child_value_str; length = true),
+            "value_child.child_value_str.length"
+        );
+    }
+
+    #[test]
+    fn struct_path_include_root() {
+        assert_eq!(
+            path!(TestStructParent::value_str; include_root = "true", case = "camel"),
+            "testStructParent.valueStr"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_child, TestStructChild::child_value_str; include_root = true),
+            "TestStructParent.value_child.child_value_str"
+        );
+        assert_eq!(
+            paths!(TestStructParent::{ value_str }, crate::tests::TestStructChild::{ child_value_num }; include_root = true, case = "pascal"),
+            ["TestStructParent.ValueStr", "TestStructChild.ChildValueNum"]
+        );
+    }
+
+    #[test]
+    fn struct_push_path() {
+        assert_eq!(push_path!(TestStructOrder::items), "items");
+        assert_eq!(
+            push_path!(TestStructOrder::opt_order~items; case = "camel"),
+            "optOrder.items"
+        );
+    }
+
+    #[allow(dead_code, non_snake_case)]
+    pub struct TestStructHttp {
+        pub httpURL: TestStructHttpStatus,
+        pub http_headers: Vec<String>,
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructHttpStatus {
+        pub status_code: u16,
+    }
+
+    #[test]
+    fn struct_path_raw_segments() {
+        assert_eq!(
+            path!(TestStructHttp::httpURL@raw.status_code; case = "camel"),
+            "httpURL.statusCode"
+        );
+        assert_eq!(
+            paths!(TestStructHttp::{ httpURL@raw.status_code, http_headers }; case = "pascal"),
+            ["httpURL.StatusCode", "HttpHeaders"]
+        );
+        assert_eq!(
+            path!(TestStructHttp::httpURL, TestStructHttpStatus::status_code@raw; case = "snake"),
+            "http_url.status_code"
+        );
+    }
+
+    assert_unique_paths!([
+        path!(TestStructParent::value_str),
+        path!(TestStructParent::value_child.child_value_str),
+        path!(TestStructChild::child_value_str),
+        path!(TestStructParent::value_str; case = "camel"),
+    ]);
+
+    #[test]
+    fn struct_assert_unique_paths() {
+        assert_unique_paths!(
+            path!(TestStructParent::value_num),
+            path!(TestStructChild::child_value_num)
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructSettings {
+        pub by_name: std::collections::HashMap<String, TestStructChild>,
+    }
+
+    #[test]
+    fn struct_path_literal_segments() {
+        assert_eq!(
+            path!(TestStructSettings::by_name."config".child_value_str),
+            "by_name.config.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructSettings::by_name."user_config.v2".child_value_str; case = "camel", delim = "/"),
+            "byName/user_config.v2/childValueStr"
+        );
+        assert_eq!(
+            paths!(TestStructSettings::{ by_name."config", by_name."defaults".child_value_num }; case = "pascal"),
+            ["ByName.config", "ByName.defaults.ChildValueNum"]
+        );
+    }
+
+    #[test]
+    fn struct_path_map_values() {
+        assert_eq!(
+            path!(TestStructSettings::by_name{*}child_value_str),
+            "by_name.*.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructSettings::by_name{"$key"}child_value_num; case = "camel", delim = "/"),
+            "byName/$key/childValueNum"
+        );
+        assert_eq!(
+            paths!(TestStructSettings::{ by_name{*}child_value_str, by_name{*} }),
+            ["by_name.*.child_value_str", "by_name.*"]
+        );
+    }
+
+    #[test]
+    fn struct_paths_btree() {
+        let test_renames = paths_btree!(TestStructParent::{ value_str, value_num, opt_value_child~child_value_str }; case = "camel");
+        assert_eq!(
+            test_renames.into_iter().collect::<Vec<(&str, &str)>>(),
+            [
+                (
+                    "opt_value_child.child_value_str",
+                    "optValueChild.childValueStr"
+                ),
+                ("value_num", "valueNum"),
+                ("value_str", "valueStr"),
+            ]
+        );
+    }
+
+    #[test]
+    fn struct_path_jsonpointer_format() {
+        let test_pointer =
+            path!(TestStructParent::value_child.child_value_str; format = "jsonpointer");
+        assert_eq!(test_pointer, "/value_child/child_value_str");
+
+        let test_escaped_pointer = path!(TestStructSettings::by_name."a/b~c".child_value_num; format = "jsonpointer", case = "camel");
+        assert_eq!(test_escaped_pointer, "/byName/a~1b~0c/childValueNum");
+
+        let json = serde_json::json!({ "byName": { "a/b~c": { "childValueNum": 42 } } });
+        assert_eq!(
+            json.pointer(test_escaped_pointer),
+            Some(&serde_json::json!(42))
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructShared {
+        pub shared: std::sync::Arc<TestStructChild>,
+        pub opt_shared: Option<std::sync::Arc<TestStructChild>>,
+    }
+
+    #[test]
+    fn struct_path_deref_steps() {
+        assert_eq!(
+            path!(TestStructShared::shared * child_value_str),
+            "shared.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructShared::opt_shared~*child_value_num; case = "camel"),
+            "optShared.childValueNum"
+        );
+        assert_eq!(
+            paths!(TestStructShared::{ shared*child_value_str, opt_shared~*child_value_num }),
+            ["shared.child_value_str", "opt_shared.child_value_num"]
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructBoxed {
+        pub boxed: Box<TestStructChild>,
+        pub opt_boxed: Option<Box<TestStructChild>>,
+    }
+
+    #[test]
+    fn struct_path_smart_pointers() {
+        assert_eq!(
+            path!(TestStructBoxed::boxed.child_value_str),
+            "boxed.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructBoxed::boxed * child_value_str),
+            "boxed.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructBoxed::opt_boxed~child_value_num),
+            "opt_boxed.child_value_num"
+        );
+        assert_eq!(
+            path!(TestStructBoxed::opt_boxed~*child_value_num),
+            "opt_boxed.child_value_num"
+        );
+        assert_eq!(
+            path!(TestStructShared::shared.child_value_str),
+            "shared.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructShared::opt_shared~child_value_num),
+            "opt_shared.child_value_num"
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructDoubleOptional {
+        pub opt_opt_child: Option<Option<TestStructChild>>,
+    }
+
+    #[test]
+    fn struct_path_double_optional() {
+        assert_eq!(
+            path!(TestStructDoubleOptional::opt_opt_child~~child_value_str),
+            "opt_opt_child.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructDoubleOptional::opt_opt_child~~child_value_num; strict = true, case = "camel"),
+            "optOptChild.childValueNum"
+        );
+    }
+
+    #[test]
+    fn struct_path_element_steps() {
+        assert_eq!(
+            path!(TestStructIndexed::value_arr#child_value_str),
+            "value_arr.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructIndexed::matrix##child_value_num; case = "camel"),
+            "matrix.childValueNum"
+        );
+        assert_eq!(
+            paths!(TestStructIndexed::{ value_arr#child_value_str, matrix##child_value_num }; delim = "/"),
+            ["value_arr/child_value_str", "matrix/child_value_num"]
+        );
+    }
+
+    pub struct TestResultError;
+
+    #[allow(dead_code)]
+    pub struct TestStructResults {
+        pub result_child: Result<TestStructChild, TestResultError>,
+        pub result_opt_child: Result<Option<TestStructChild>, TestResultError>,
+    }
+
+    #[test]
+    fn struct_path_result_steps() {
+        assert_eq!(
+            path!(TestStructResults::result_child?child_value_str),
+            "result_child.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructResults::result_opt_child?~child_value_num; case = "camel"),
+            "resultOptChild.childValueNum"
+        );
+        assert_eq!(
+            paths!(TestStructResults::{ result_child?child_value_str, result_opt_child?~child_value_num }),
+            [
+                "result_child.child_value_str",
+                "result_opt_child.child_value_num"
+            ]
+        );
+    }
+
+    #[test]
+    fn struct_paths_graphql_format() {
+        let test_selection = paths!(TestStructParent::{ value_str, value_child.child_value_str, value_child.child_value_num }; format = "graphql", case = "camel");
+        assert_eq!(
+            test_selection,
+            "valueStr valueChild { childValueStr childValueNum }"
+        );
+
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; format = "graphql"),
+            "value_child { child_value_str }"
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructKeywords {
+        pub type_: String,
+        pub ref__: String,
+    }
+
+    #[test]
+    fn struct_path_strip_trailing_underscore() {
+        assert_eq!(
+            path!(TestStructKeywords::type_; strip_trailing_underscore = true),
+            "type"
+        );
+        assert_eq!(
+            path!(TestStructKeywords::ref__; strip_trailing_underscore = true),
+            "ref_"
+        );
+        assert_eq!(path!(TestStructKeywords::type_), "type_");
+    }
+
+    #[test]
+    fn struct_path_field_mask() {
+        const TEST_MASK: &str = field_mask!(TestStructParent::{ value_str, value_child.child_value_str }; case = "camel");
+        assert_eq!(TEST_MASK, "valueStr,valueChild.childValueStr");
+    }
+
+    #[test]
+    fn struct_path_positional_operator() {
+        assert_eq!(
+            path!(TestStructOrder::items[$].child_value_str),
+            "items.$.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructOrder::items[$].child_value_num; case = "camel", delim = "/"),
+            "items/$/childValueNum"
+        );
+    }
+
+    #[test]
+    fn struct_path_pipeline() {
+        assert_eq!(
+            path!(TestStructParent::value_str; abbrev = "valueStr=vStr", case = "camel"),
+            "valueStr"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_str; abbrev = "valueStr=vStr", case = "camel", pipeline = "case,abbrev"),
+            "vStr"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_str; prefix = "meta_data", case = "camel", pipeline = "prefix,case"),
+            "metaData.valueStr"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_str; prefix = "meta_data", pipeline = "prefix,case"),
+            "meta_data.value_str"
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructIndexed {
+        pub value_arr: [TestStructChild; 2],
+        pub matrix: Vec<Vec<TestStructChild>>,
+        pub opt_items: Vec<Option<TestStructChild>>,
+    }
+
+    #[test]
+    fn struct_path_literal_index() {
+        assert_eq!(
+            path!(TestStructIndexed::value_arr[0].child_value_str),
+            "value_arr[0].child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructIndexed::matrix[0][1].child_value_num; case = "camel"),
+            "matrix[0][1].childValueNum"
+        );
+        assert_eq!(
+            path!(TestStructIndexed::opt_items[1]~child_value_str),
+            "opt_items[1].child_value_str"
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct User {
+        pub address: UserAddress,
+        pub created_at: u64,
+    }
+
+    #[allow(dead_code)]
+    pub struct UserAddress {
+        pub city: String,
+    }
+
+    #[test]
+    fn struct_path_template() {
+        assert_eq!(
+            path!(User::address.city; template = "{struct}[{path}]"),
+            "User[address.city]"
+        );
+        assert_eq!(
+            paths!(TestStructParent::{ value_str, value_num }; template = "{struct}:{path}", case = "kebab"),
+            [
+                "test-struct-parent:value-str",
+                "test-struct-parent:value-num"
+            ]
+        );
+        assert_eq!(
+            path!(User::address.city; template = "log.{path}"),
+            "log.address.city"
+        );
+    }
+
+    #[test]
+    fn struct_path_copy() {
+        assert_eq!(path_copy!(TestStructParent::value_num), "value_num");
+        assert_eq!(
+            path_copy!(TestStructParent::value_child.child_value_num; case = "camel"),
+            "valueChild.childValueNum"
+        );
+    }
+
+    #[test]
+    fn struct_path_wildcard_index() {
+        assert_eq!(
+            path!(TestStructIndexed::value_arr[*].child_value_str),
+            "value_arr[*].child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructIndexed::matrix[*][*].child_value_num; case = "camel"),
+            "matrix[*][*].childValueNum"
+        );
+    }
+
+    #[test]
+    fn struct_path_annotated() {
+        assert_eq!(
+            path_annotated!(TestStructOrder::items[0].child_value_str; case = "camel"),
+            [
+                ("items", "field"),
+                ("0", "index"),
+                ("childValueStr", "field")
+            ]
+        );
+        assert_eq!(
+            path_annotated!(TestStructIndexed::value_arr[*].child_value_num),
+            [
+                ("value_arr", "field"),
+                ("*", "wildcard"),
+                ("child_value_num", "field")
+            ]
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestPoint(pub f64, pub f64);
+
+    #[allow(dead_code)]
+    pub struct TestStructWrapper {
+        pub pair: (TestStructChild, TestPoint),
+        pub opt_point: Option<TestPoint>,
+    }
+
+    #[test]
+    fn struct_path_tuple_fields() {
+        assert_eq!(path!(TestPoint::0), "0");
+        assert_eq!(path!(TestStructWrapper::pair.1), "pair.1");
+        assert_eq!(path!(TestStructWrapper::pair.1 .0), "pair.1.0");
+        assert_eq!(
+            path!(TestStructWrapper::pair.0.child_value_str; case = "camel"),
+            "pair.0.childValueStr"
+        );
+        assert_eq!(path!(TestStructWrapper::opt_point~1), "opt_point.1");
+        assert_eq!(paths!(TestPoint::{ 0, 1 }), ["0", "1"]);
+    }
+
+    #[test]
+    fn struct_path_escaped_literals() {
+        assert_eq!(
+            path!(TestStructSettings::by_name."a\"b\u{e9}".child_value_str),
+            "by_name.a\"b\u{e9}.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_str; prefix = r#"meta"data"#),
+            "meta\"data.value_str"
+        );
+        assert_eq!(
+            paths!(TestStructSettings::{ by_name."a\\b" }),
+            ["by_name.a\\b"]
+        );
+    }
+
+    define_column_ref!();
+
+    #[test]
+    fn struct_path_column_ref() {
+        let test_column = column_ref!(User::created_at);
+        assert_eq!(test_column.db, "created_at");
+        assert_eq!(test_column.api, "createdAt");
+
+        assert_eq!(
+            column_ref!(TestStructParent::value_child.child_value_str; delim = "/"),
+            ColumnRef {
+                db: "value_child/child_value_str",
+                api: "valueChild/childValueStr"
+            }
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestMachine {
+        pub state: TestMachineState,
+    }
+
+    #[allow(dead_code)]
+    pub enum TestMachineState {
+        Idle,
+        Running {
+            progress: u8,
+            current: TestStructChild,
+        },
+        Paused(u8, TestStructChild),
+    }
+
+    #[test]
+    fn struct_path_enum_field() {
+        assert_eq!(
+            path!(TestMachine::state::TestMachineState::Running::progress),
+            "state.progress"
+        );
+        assert_eq!(
+            path!(TestMachine::state::TestMachineState::Running::current.child_value_str; case = "camel"),
+            "state.current.childValueStr"
+        );
+        assert_eq!(
+            path!(TestMachine::state::TestMachineState::Paused::1.child_value_num),
+            "state.1.child_value_num"
+        );
+        assert_eq!(
+            paths!(TestMachine::state::TestMachineState::Running::progress, TestStructChild::{child_value_str, child_value_num}),
+            ["state.progress", "child_value_str", "child_value_num"]
+        );
+    }
+
+    #[test]
+    fn struct_path_reserved() {
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; reserved = "_id, __typename"),
+            "value_child.child_value_str"
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestContainer<T> {
+        pub value: T,
+        pub child: TestStructChild,
+    }
+
+    #[allow(dead_code)]
+    pub struct TestPair<'a, A, B> {
+        pub first: &'a A,
+        pub second: Vec<B>,
+    }
+
+    #[test]
+    fn struct_path_turbofish() {
+        assert_eq!(path!(TestContainer::<u64>::value), "value");
+        assert_eq!(
+            path!(TestContainer::<Vec<u64>>::child.child_value_str; case = "camel"),
+            "child.childValueStr"
+        );
+        assert_eq!(path!(TestPair::<'static, u64, String>::second), "second");
+        assert_eq!(
+            path!(crate::tests::TestContainer::<u64>::value; include_root = true),
+            "TestContainer.value"
+        );
+        assert_eq!(
+            paths!(TestContainer::<String>::{ value, child.child_value_num }),
+            ["value", "child.child_value_num"]
+        );
+    }
+
+    #[test]
+    fn struct_path_rename_map() {
+        let test_renames = rename_map!(User::{ created_at, address.city }; case = "camel");
+        assert_eq!(
+            test_renames,
+            [
+                ("createdAt", "created_at"),
+                ("address.city", "address.city")
+            ]
+        );
+        assert_eq!(
+            rename_map!(TestStructParent::{ opt_value_child~child_value_str }; delim = "/"),
+            [(
+                "opt_value_child/child_value_str",
+                "opt_value_child.child_value_str"
+            )]
+        );
+    }
+
+    #[test]
+    fn struct_path_as() {
+        assert_eq!(path_as!(User::created_at as u64), ("created_at", "u64"));
+        assert_eq!(
+            path_as!(TestStructParent::value_child.child_value_str as String; case = "camel"),
+            ("valueChild.childValueStr", "String")
+        );
+        assert_eq!(
+            path_as!(TestStructIndexed::value_arr as [TestStructChild; 2]),
+            ("value_arr", "[TestStructChild; 2]")
+        );
+        assert_eq!(
+            path_as!(TestStructIndexed::opt_items as Vec<Option<TestStructChild>>),
+            ("opt_items", "Vec<Option<TestStructChild>>")
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructHolder {
+        pub parent: TestStructParent,
+    }
+
+    #[test]
+    fn struct_path_boundary() {
+        assert_eq!(
+            path!(User::address, UserAddress::city; boundary = "->"),
+            "address->city"
+        );
+        assert_eq!(
+            path!(TestStructHolder::parent, TestStructParent::value_child.child_value_str; boundary = "->", case = "camel"),
+            "parent->valueChild.childValueStr"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; boundary = "->"),
+            "value_child.child_value_str"
+        );
+    }
+
+    #[test]
+    fn struct_path_segment_count() {
+        const TEST_NESTED_COUNT: usize =
+            segment_count!(TestStructParent::value_child.child_value_str);
+        assert_eq!(TEST_NESTED_COUNT, 2);
+        assert_eq!(segment_count!(TestStructParent::value_str), 1);
+        assert_eq!(
+            segment_count!(TestStructParent::opt_value_child~child_value_str),
+            2
         );
-        assert_eq!(test_mixed_path, "value_str.child_value_str");
+        assert_eq!(
+            segment_count!(TestStructIndexed::matrix[0][1].child_value_num),
+            2
+        );
+        assert_eq!(
+            segment_count!(TestStructByKind::by_kind[TestKind::Primary].child_value_str),
+            3
+        );
+    }
 
-        let test_opt_child = path!(TestStructParent::opt_value_child~child_value_str);
-        assert_eq!(test_opt_child, "opt_value_child.child_value_str");
+    #[test]
+    fn struct_path_opt() {
+        assert_eq!(path_opt!(), "");
+        assert_eq!(path_opt!(; case = "camel"), "");
+        assert_eq!(
+            path_opt!(TestStructParent::value_child.child_value_str; case = "camel"),
+            "valueChild.childValueStr"
+        );
+    }
+
+    const_path!(pub TEST_VALUE_STR_PATH = TestStructParent::value_child.child_value_str; case = "camel");
+    const_path!(TEST_PAIR_PATH = TestTupleVariant::Pair::1);
+
+    #[test]
+    fn struct_path_const() {
+        const TEST_PATH: &str = path!(TestStructParent::value_str);
+        assert_eq!(TEST_PATH, "value_str");
+        assert_eq!(TEST_VALUE_STR_PATH, "valueChild.childValueStr");
+        assert_eq!(TEST_PAIR_PATH, "Pair.1");
+    }
+
+    assert_path_prefix!(User::address, User::address.city);
+    assert_path_prefix!(TestStructParent::opt_value_child, TestStructParent::opt_value_child~child_value_str; case = "camel");
+
+    #[test]
+    fn struct_assert_path_prefix() {
+        assert_path_prefix!(TestStructParent::value_child, TestStructParent::value_child);
+        assert_path_prefix!(TestStructIndexed::matrix, TestStructIndexed::matrix[0][1].child_value_num; delim = "/");
+    }
+
+    #[test]
+    fn struct_path_string() {
+        let mut test_path: String = path_string!(TestStructParent::value_child.child_value_str; delim = "/", case = "camel");
+        assert_eq!(
+            test_path,
+            path!(TestStructParent::value_child.child_value_str; delim = "/", case = "camel")
+        );
+        test_path.push_str("/key");
+        assert_eq!(test_path, "valueChild/childValueStr/key");
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructIdentified {
+        pub id: u64,
+        pub owner: TestStructIdentifiedOwner,
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructIdentifiedOwner {
+        pub user_id: u64,
+        pub url: String,
+    }
+
+    #[test]
+    fn struct_path_acronym_segments() {
+        assert_eq!(
+            path!(TestStructIdentified::id; case = "camel", acronym_segments = "upper"),
+            "ID"
+        );
+        assert_eq!(
+            paths!(TestStructIdentified::{ owner.user_id, owner.url }; case = "camel", acronym_segments = "upper"),
+            ["owner.userId", "owner.URL"]
+        );
+        assert_eq!(
+            path!(TestStructIdentified::id; case = "camel", acronym_segments = "keep"),
+            "id"
+        );
+    }
+
+    #[test]
+    fn struct_path_vec() {
+        let test_segments: Vec<&'static str> =
+            path_vec!(TestStructParent::value_child.child_value_str);
+        assert_eq!(test_segments, vec!["value_child", "child_value_str"]);
+        assert_eq!(
+            path_vec!(TestStructParent::opt_value_child~child_value_num; case = "camel", delim = "/"),
+            vec!["optValueChild", "childValueNum"]
+        );
+    }
+
+    #[test]
+    fn struct_path_version() {
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; version = "v2"),
+            "v2.value_child.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; version = "V2", case = "camel", delim = "/"),
+            "V2/valueChild/childValueStr"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_child, TestStructChild::child_value_str; version = "v2", boundary = "->"),
+            "v2.value_child->child_value_str"
+        );
+        assert_eq!(
+            path_dyn_delim!(TestStructParent::value_child.child_value_str, "/"; version = "v2"),
+            "v2/value_child/child_value_str"
+        );
+        assert_eq!(
+            at_depth!(TestStructParent::value_child.child_value_str, 1; version = "v2"),
+            "child_value_str"
+        );
+    }
+
+    #[test]
+    fn struct_paths_map() {
+        let test_paths = paths_map!(TestStructParent::{ value_str, value_child.child_value_str }; case = "camel");
+        assert_eq!(test_paths.len(), 2);
+        assert_eq!(test_paths["value_str"], "valueStr");
+        assert_eq!(test_paths["child_value_str"], "valueChild.childValueStr");
+    }
+
+    #[allow(dead_code)]
+    #[derive(StructPath)]
+    #[struct_path(case = "camel")]
+    pub struct TestStructNested {
+        pub value_str: String,
+        #[struct_path(nested)]
+        pub value_child: TestStructNestedChild,
+        #[struct_path(nested)]
+        pub opt_value_child: Option<TestStructNestedChild>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(StructPath)]
+    #[struct_path(case = r"camel")]
+    pub struct TestStructNestedChild {
+        pub child_value_str: String,
+    }
+
+    #[test]
+    fn struct_path_derived_consts() {
+        assert_eq!(TestStructDerivedPath::VALUE_STR, "value_str");
+        assert_eq!(TestStructDerivedPath::TYPE, "type");
+        assert_eq!(TestStructNestedPath::VALUE_STR, "valueStr");
+        assert_eq!(
+            TestStructNestedPath::value_child(TestStructNestedChildPath::CHILD_VALUE_STR),
+            "valueChild.childValueStr"
+        );
+        assert_eq!(
+            TestStructNestedPath::opt_value_child(TestStructNestedChildPath::CHILD_VALUE_STR),
+            "optValueChild.childValueStr"
+        );
+    }
+
+    define_path_doc!();
+
+    #[test]
+    fn struct_path_doc() {
+        let test_doc = path_doc!(TestStructParent::value_child.child_value_str);
+        assert_eq!(test_doc.path, "value_child.child_value_str");
+        assert_eq!(
+            test_doc.doc,
+            "Field child_value_str of ValueChild within TestStructParent"
+        );
+        let test_doc = path_doc!(TestStructParent::value_child, TestStructChild::child_value_str; case = "camel");
+        assert_eq!(test_doc.path, "valueChild.childValueStr");
+        assert_eq!(
+            test_doc.doc,
+            "Field child_value_str of TestStructChild within TestStructParent"
+        );
+        assert_eq!(
+            path_doc!(TestStructParent::value_str),
+            PathDoc {
+                path: "value_str",
+                doc: "Field value_str of TestStructParent"
+            }
+        );
+    }
+
+    #[allow(dead_code)]
+    #[derive(StructPath)]
+    pub struct TestStructTree {
+        pub name: String,
+        #[struct_path(nested)]
+        pub value_child: TestStructNestedChild,
+        #[struct_path(nested)]
+        pub children: Option<Box<TestStructTree>>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(StructPath)]
+    pub struct TestStructPage<T> {
+        pub items: Vec<T>,
+        #[struct_path(nested)]
+        pub value_child: TestStructNestedChild,
+    }
+
+    #[test]
+    fn struct_path_derived_all_paths() {
+        assert_eq!(
+            TestStructDerived::all_paths(),
+            vec!["value_str", "value_map", "type"]
+        );
+        assert_eq!(
+            TestStructNested::all_paths(),
+            vec![
+                "valueStr",
+                "valueChild.childValueStr",
+                "optValueChild.childValueStr"
+            ]
+        );
+        assert_eq!(
+            TestStructTree::all_paths(),
+            vec!["name", "value_child.childValueStr", "children"]
+        );
+        assert_eq!(
+            TestStructPage::<u64>::all_paths(),
+            TestStructPage::<String>::all_paths()
+        );
+        assert_eq!(
+            TestStructPage::<u64>::all_paths(),
+            vec!["items", "value_child.childValueStr"]
+        );
+    }
+
+    #[test]
+    fn struct_path_checksum() {
+        assert_eq!(
+            path!(TestStructParent::value_str; checksum = true),
+            "value_str.b5b6"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; case = "camel", checksum = true),
+            "valueChild.childValueStr.f0ac"
+        );
+        assert_eq!(
+            paths!(TestStructParent::{ value_str, value_num }; checksum = true),
+            ["value_str.b5b6", "value_num.828d"]
+        );
+    }
+
+    define_path_macros!(camel_path => path, camel_paths => paths; case = "camel", delim = ".");
+
+    #[test]
+    fn struct_define_path_macros() {
+        assert_eq!(
+            camel_path!(TestStructParent::value_child.child_value_str),
+            "valueChild.childValueStr"
+        );
+        assert_eq!(
+            camel_path!(TestStructParent::value_child.child_value_str; delim = "/"),
+            "valueChild/childValueStr"
+        );
+        assert_eq!(
+            camel_paths!(TestStructParent::{ value_str, value_num }),
+            ["valueStr", "valueNum"]
+        );
+    }
+
+    with_root!(TestStructParent => {
+        const TEST_ROOT_PATH: &str = path!(.value_child.child_value_str; case = "camel");
+        const TEST_ROOT_PATHS: [&str; 2] = paths!({ value_str, value_num });
+    });
+
+    #[test]
+    fn struct_path_with_root() {
+        assert_eq!(TEST_ROOT_PATH, "valueChild.childValueStr");
+        assert_eq!(TEST_ROOT_PATHS, ["value_str", "value_num"]);
+
+        let (test_root_path, test_root_paths) = with_root!(crate::tests::TestStructParent => (
+            path!(.opt_value_child~child_value_str),
+            paths!({ value_str, value_child.child_value_str })
+        ));
+        assert_eq!(test_root_path, "opt_value_child.child_value_str");
+        assert_eq!(
+            test_root_paths,
+            ["value_str", "value_child.child_value_str"]
+        );
+
+        let test_mixed_paths = with_root!(TestStructParent => [
+            at_depth!(.value_child.child_value_str, 1),
+            path!(TestStructChild::child_value_num)
+        ]);
+        assert_eq!(test_mixed_paths, ["child_value_str", "child_value_num"]);
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructUrl {
+        pub café_menu: TestStructChild,
+    }
+
+    #[test]
+    fn struct_path_url_format() {
+        let test_url = path!(TestStructParent::value_child.child_value_str; format = "url");
+        assert_eq!(test_url, "value_child/child_value_str");
+
+        let test_url_encoded =
+            path!(TestStructUrl::café_menu.child_value_str; format = "url", case = "camel");
+        assert_eq!(test_url_encoded, "caf%C3%A9Menu/childValueStr");
+
+        let test_url_opt =
+            paths!(TestStructParent::opt_value_child~child_value_str; format = "url");
+        assert_eq!(test_url_opt, ["opt_value_child/child_value_str"]);
+    }
+
+    #[test]
+    fn struct_path_json_schema_format() {
+        let test_json_schema =
+            path!(TestStructParent::value_child.child_value_str; format = "json-schema");
+        assert_eq!(
+            test_json_schema,
+            "#/properties/value_child/properties/child_value_str"
+        );
+
+        let test_json_schema_case = paths!(TestStructParent::{ value_str, opt_value_child~child_value_str }; format = "json-schema", case = "camel");
+        assert_eq!(
+            test_json_schema_case,
+            [
+                "#/properties/valueStr",
+                "#/properties/optValueChild/properties/childValueStr"
+            ]
+        );
+    }
+
+    #[test]
+    fn struct_path_root_first() {
+        let test_root_first = path!(
+            TestStructParent::value_child,
+            TestStructChild::child_value_str; root_first = true
+        );
+        assert_eq!(
+            test_root_first,
+            "test_struct_parent.value_child.child_value_str"
+        );
+
+        let test_root_first_case = path!(
+            crate::tests::TestStructParent::value_child,
+            TestStructChild::child_value_str; root_first = true, case = "camel", delim = "/"
+        );
+        assert_eq!(
+            test_root_first_case,
+            "testStructParent/valueChild/childValueStr"
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructGrandParent {
+        pub value_parent: TestStructParent,
+    }
+
+    #[test]
+    fn struct_path_flatten_delim() {
+        assert_eq!(
+            path!(TestStructGrandParent::value_parent.value_child.child_value_str; delim = "/", flatten_delim = "."),
+            "value_parent/value_child.child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_child.child_value_str; delim = "/", flatten_delim = "."),
+            "value_child/child_value_str"
+        );
+        assert_eq!(
+            path!(TestStructParent::value_str; delim = "/", flatten_delim = "."),
+            "value_str"
+        );
+    }
+
+    #[test]
+    fn struct_path_strict() {
+        let test_strict = path!(TestStructParent::opt_value_child~child_value_str; strict = true);
+        assert_eq!(test_strict, "opt_value_child.child_value_str");
+
+        let test_strict_multiple =
+            paths!(TestStructParent::{ value_str, opt_value_child~child_value_str }; strict = true);
+        assert_eq!(
+            test_strict_multiple,
+            ["value_str", "opt_value_child.child_value_str"]
+        );
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructOrder {
+        pub items: Vec<TestStructChild>,
+        pub opt_order: Option<Box<TestStructOrder>>,
+    }
+
+    #[test]
+    fn struct_path_slice_ranges() {
+        let test_range = path!(TestStructOrder::items[1..3].child_value_str);
+        assert_eq!(test_range, "items[1..3].child_value_str");
+
+        let test_open_range =
+            path!(TestStructOrder::items[..].child_value_str; delim = "/", case = "camel");
+        assert_eq!(test_open_range, "items[..]/childValueStr");
+
+        let test_range_forms = paths!(TestStructOrder::{ items[2..].child_value_str, items[..=1].child_value_num, opt_order~items[..2] });
+        assert_eq!(
+            test_range_forms,
+            [
+                "items[2..].child_value_str",
+                "items[..=1].child_value_num",
+                "opt_order.items[..2]"
+            ]
+        );
     }
 
     #[test]
@@ -56,4 +1717,324 @@ mod tests {
         );
         assert_eq!(test_multiple_types, ["opt_value_child.child_value_str"]);
     }
+
+    #[test]
+    fn struct_path_at_depth() {
+        let test_first = at_depth!(TestStructParent::value_child.child_value_str, 0);
+        assert_eq!(test_first, "value_child");
+
+        let test_last = at_depth!(TestStructParent::value_child.child_value_str, 1);
+        assert_eq!(test_last, "child_value_str");
+
+        let test_case = at_depth!(TestStructParent::value_child.child_value_str, 1; case = "camel");
+        assert_eq!(test_case, "childValueStr");
+
+        let test_opt_child = at_depth!(TestStructParent::opt_value_child~child_value_str, 1);
+        assert_eq!(test_opt_child, "child_value_str");
+
+        let test_positional = at_depth!(TestStructOrder::items[$].child_value_str, 1);
+        assert_eq!(test_positional, "$");
+    }
+
+    #[test]
+    fn struct_paths_json() {
+        let test_json = paths_json!(TestStructParent::{ value_str, value_num });
+        assert_eq!(test_json, r#"["value_str","value_num"]"#);
+
+        let test_json_options = paths_json!(
+            TestStructParent::value_child.child_value_str,
+            TestStructParent::opt_value_child~child_value_str; delim = "/", case = "camel"
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<String>>(test_json_options).unwrap(),
+            ["valueChild/childValueStr", "optValueChild/childValueStr"]
+        );
+    }
+
+    #[test]
+    fn struct_paths_grouped() {
+        let test_grouped = paths_grouped!(
+            TestStructParent::{ value_str, value_num },
+            crate::tests::TestStructChild::{ child_value_str }
+        );
+        assert_eq!(
+            test_grouped,
+            [
+                ("TestStructParent", &["value_str", "value_num"] as &[&str]),
+                ("TestStructChild", &["child_value_str"] as &[&str])
+            ]
+        );
+
+        let test_grouped_options = paths_grouped!(
+            TestStructParent::value_child.child_value_str,
+            TestStructChild::child_value_num; case = "camel"
+        );
+        assert_eq!(test_grouped_options[0].1, ["valueChild.childValueStr"]);
+        assert_eq!(
+            test_grouped_options[1],
+            ("TestStructChild", &["childValueNum"] as &[&str])
+        );
+    }
+
+    #[test]
+    fn struct_path_via() {
+        let test_via = path_via!(
+            |p: &TestStructParent| &p.value_child,
+            TestStructChild::child_value_str
+        );
+        assert_eq!(test_via, "child_value_str");
+
+        let test_via_options = path_via!(
+            |p: &TestStructParent| -> &TestStructChild { &p.value_child },
+            TestStructChild::child_value_str; case = "camel"
+        );
+        assert_eq!(test_via_options, "childValueStr");
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructAddress {
+        pub line_1: String,
+        pub line_2: String,
+    }
+
+    #[test]
+    fn struct_path_digit_style() {
+        let test_default = path!(TestStructAddress::line_2; case = "camel");
+        assert_eq!(test_default, "line2");
+
+        let test_glued = path!(TestStructAddress::line_2; case = "camel", digit_style = "glued");
+        assert_eq!(test_glued, "line2");
+
+        let test_separated =
+            path!(TestStructAddress::line_2; case = "camel", digit_style = "separated");
+        assert_eq!(test_separated, "line_2");
+
+        let test_separated_pascal = paths!(TestStructAddress::{ line_1, line_2 }; case = "pascal", digit_style = "separated");
+        assert_eq!(test_separated_pascal, ["Line_1", "Line_2"]);
+    }
+
+    #[allow(dead_code)]
+    #[derive(serde::Serialize)]
+    pub enum TestMessage {
+        Login {
+            user: String,
+            child: TestStructChild,
+        },
+        Logout,
+    }
+
+    #[test]
+    fn struct_enum_path() {
+        let test_variant_field = enum_path!(TestMessage::Login::user);
+        assert_eq!(test_variant_field, "Login.user");
+
+        let test_nested =
+            enum_path!(TestMessage::Login::child.child_value_str; delim = "/", case = "camel");
+        assert_eq!(test_nested, "Login/child/childValueStr");
+
+        let test_full_path = enum_path!(crate::tests::TestMessage::Login::user);
+        assert_eq!(test_full_path, "Login.user");
+
+        let json = serde_json::to_value(TestMessage::Login {
+            user: "test-user".to_string(),
+            child: TestStructChild {
+                child_value_str: "test-str".to_string(),
+                child_value_num: 42,
+            },
+        })
+        .unwrap();
+        let json_pointer = format!("/{}", test_variant_field.replace('.', "/"));
+        assert_eq!(
+            json.pointer(&json_pointer),
+            Some(&serde_json::json!("test-user"))
+        );
+    }
+
+    #[allow(dead_code)]
+    #[derive(serde::Serialize)]
+    pub struct TestEntity {
+        pub id: u64,
+    }
+
+    #[allow(dead_code)]
+    #[derive(serde::Serialize)]
+    pub enum TestEvent {
+        Created(TestEntity),
+        Moved(TestEntity, TestEntity),
+    }
+
+    #[test]
+    fn struct_enum_path_tuple_variant() {
+        let test_tuple_variant = enum_path!(TestEvent::Created::0.id);
+        assert_eq!(test_tuple_variant, "Created.0.id");
+
+        let test_tuple_variant_second = enum_path!(TestEvent::Moved::1.id);
+        assert_eq!(test_tuple_variant_second, "Moved.1.id");
+
+        let json =
+            serde_json::to_value(TestEvent::Moved(TestEntity { id: 1 }, TestEntity { id: 2 }))
+                .unwrap();
+        let json_pointer = format!("/{}", test_tuple_variant_second.replace('.', "/"));
+        assert_eq!(json.pointer(&json_pointer), Some(&serde_json::json!(2)));
+    }
+
+    #[allow(dead_code)]
+    #[derive(serde::Serialize)]
+    pub enum TestTupleVariant {
+        Pair(u32, String),
+    }
+
+    #[test]
+    fn struct_path_tuple_variant() {
+        let test_tagged = path!(TestTupleVariant::Pair::1);
+        assert_eq!(test_tagged, "Pair.1");
+        assert_eq!(path!(TestTupleVariant::Pair::1; tagged = false), "1");
+        assert_eq!(path!(TestEvent::Moved::1.id; delim = "/"), "Moved/1/id");
+
+        let json = serde_json::to_value(TestTupleVariant::Pair(1, "test-str".to_string())).unwrap();
+        let json_pointer = format!("/{}", test_tagged.replace('.', "/"));
+        assert_eq!(
+            json.pointer(&json_pointer),
+            Some(&serde_json::json!("test-str"))
+        );
+    }
+
+    #[allow(dead_code)]
+    #[derive(StructPath, serde::Serialize)]
+    pub struct TestStructEntity {
+        pub id: u64,
+        pub entity_name: String,
+    }
+
+    #[derive(StructPath, serde::Serialize)]
+    #[serde(transparent)]
+    pub struct TestTransparentEntity {
+        #[struct_path(nested)]
+        pub entity: TestStructEntity,
+    }
+
+    #[derive(StructPath, serde::Serialize)]
+    #[serde(transparent)]
+    pub struct TestTransparentId {
+        pub id: u64,
+    }
+
+    #[derive(StructPath, serde::Serialize)]
+    pub struct TestStructTransparent {
+        #[struct_path(nested)]
+        pub owner: TestTransparentEntity,
+        #[struct_path(nested)]
+        pub owner_id: TestTransparentId,
+    }
+
+    #[test]
+    fn struct_derived_transparent() {
+        let test_inlined = TestStructTransparentPath::owner(TestTransparentEntityPath::ID);
+        assert_eq!(test_inlined, "owner.id");
+        assert_eq!(
+            TestStructTransparentPath::owner(TestTransparentEntityPath::ENTITY_NAME),
+            "owner.entity_name"
+        );
+
+        let json = serde_json::to_value(TestStructTransparent {
+            owner: TestTransparentEntity {
+                entity: TestStructEntity {
+                    id: 42,
+                    entity_name: "test-name".to_string(),
+                },
+            },
+            owner_id: TestTransparentId { id: 42 },
+        })
+        .unwrap();
+        let json_pointer = format!("/{}", test_inlined.replace('.', "/"));
+        assert_eq!(json.pointer(&json_pointer), Some(&serde_json::json!(42)));
+        assert_eq!(json.pointer("/owner_id"), Some(&serde_json::json!(42)));
+
+        assert_eq!(
+            TestStructTransparent::all_paths(),
+            vec!["owner.id", "owner.entity_name", "owner_id"]
+        );
+    }
+}
+
+#[cfg(all(test, feature = "no-checks"))]
+mod no_checks_tests {
+    use struct_path::*;
+
+    #[allow(dead_code)]
+    pub struct TestStructGenerated {
+        pub value_str: String,
+    }
+
+    #[test]
+    fn struct_path_no_checks() {
+        let test_unchecked = path!(TestStructGenerated::valeu_str; case = "camel");
+        assert_eq!(test_unchecked, "valeuStr");
+
+        let test_unchecked_many = paths!(TestStructGenerated::{ value_str, value_num });
+        assert_eq!(test_unchecked_many, ["value_str", "value_num"]);
+    }
+}
+
+#[cfg(all(test, feature = "phf"))]
+mod phf_tests {
+    use struct_path::*;
+
+    #[allow(dead_code)]
+    pub struct TestStructDispatch {
+        pub value_str: String,
+        pub value_num: u64,
+        pub opt_value_flag: Option<bool>,
+    }
+
+    static TEST_DISPATCH_PATHS: phf::Map<&'static str, usize> =
+        phf_paths!(TestStructDispatch::{ value_str, value_num, opt_value_flag }; case = "camel");
+
+    #[test]
+    fn struct_phf_paths() {
+        assert_eq!(TEST_DISPATCH_PATHS.len(), 3);
+        assert_eq!(TEST_DISPATCH_PATHS.get("valueStr"), Some(&0));
+        assert_eq!(TEST_DISPATCH_PATHS.get("valueNum"), Some(&1));
+        assert_eq!(TEST_DISPATCH_PATHS.get("optValueFlag"), Some(&2));
+        assert_eq!(TEST_DISPATCH_PATHS.get("value_str"), None);
+    }
+}
+
+#[cfg(feature = "gated-module")]
+pub mod gated_module {
+    #[allow(dead_code)]
+    pub struct TestStructGated {
+        pub value_str: String,
+        pub opt_value_child: Option<TestStructGatedChild>,
+    }
+
+    #[allow(dead_code)]
+    pub struct TestStructGatedChild {
+        pub child_value_num: u64,
+    }
+
+    #[cfg(test)]
+    pub const GATED_PATH: &str =
+        struct_path::path!(TestStructGated::opt_value_child~child_value_num);
+}
+
+#[cfg(all(test, feature = "gated-module"))]
+mod gated_module_tests {
+    use struct_path::*;
+
+    #[test]
+    fn struct_path_gated_module() {
+        assert_eq!(
+            path!(crate::gated_module::TestStructGated::value_str; case = "camel"),
+            "valueStr"
+        );
+        assert_eq!(
+            paths!(crate::gated_module::TestStructGated::{ value_str, opt_value_child~child_value_num }),
+            ["value_str", "opt_value_child.child_value_num"]
+        );
+        assert_eq!(
+            crate::gated_module::GATED_PATH,
+            "opt_value_child.child_value_num"
+        );
+    }
 }
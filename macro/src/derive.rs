@@ -0,0 +1,737 @@
+//! A lightweight parser of the struct definitions for `#[derive(StructPath)]`,
+//! following the same no-deps token parsing approach as the path macros.
+
+use crate::{apply_options, parser::string_literal_value, top_level_comma_positions};
+use convert_case::{Case, Casing};
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
+use std::collections::HashMap;
+
+pub struct DeriveField {
+    pub vis: String,
+    pub name: String,
+    pub field_type: Vec<TokenTree>,
+    pub options: HashMap<String, String>,
+}
+
+pub struct DeriveStruct {
+    pub vis: String,
+    pub name: String,
+    pub generics: Vec<TokenTree>,
+    pub where_clause: Vec<TokenTree>,
+    pub fields: Vec<DeriveField>,
+    pub options: HashMap<String, String>,
+}
+
+impl DeriveStruct {
+    pub fn parse(derive_stream: TokenStream) -> Self {
+        let mut derive_tokens = derive_stream.into_iter().peekable();
+        let options = parse_struct_path_attributes(&mut derive_tokens);
+        let vis = parse_visibility(&mut derive_tokens);
+
+        match derive_tokens.next() {
+            Some(TokenTree::Ident(id)) if id.to_string() == "struct" => {}
+            others => panic!(
+                "StructPath can be derived only for structs with named fields: {:?}",
+                others
+            ),
+        }
+
+        let name = match derive_tokens.next() {
+            Some(TokenTree::Ident(id)) => id.to_string(),
+            others => panic!("Unexpected struct name: {:?}", others),
+        };
+
+        let mut generics = Vec::new();
+        if matches!(derive_tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '<') {
+            derive_tokens.next();
+            let mut angle_brackets_counter = 1;
+            let mut prev_joint_punct: Option<char> = None;
+            for token_tree in derive_tokens.by_ref() {
+                if let TokenTree::Punct(punct) = &token_tree {
+                    match punct.as_char() {
+                        '<' => angle_brackets_counter += 1,
+                        '>' if prev_joint_punct != Some('-') => angle_brackets_counter -= 1,
+                        _ => {}
+                    }
+                    prev_joint_punct =
+                        Some(punct.as_char()).filter(|_| punct.spacing() == Spacing::Joint);
+                    if angle_brackets_counter == 0 {
+                        break;
+                    }
+                } else {
+                    prev_joint_punct = None;
+                }
+                generics.push(token_tree);
+            }
+        }
+
+        let mut where_clause = Vec::new();
+        let fields_stream = loop {
+            match derive_tokens.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                    break group.stream();
+                }
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                    panic!("StructPath can be derived only for structs with named fields, not for tuple structs");
+                }
+                Some(token_tree) => where_clause.push(token_tree),
+                None => panic!("StructPath can be derived only for structs with named fields"),
+            }
+        };
+
+        DeriveStruct {
+            vis,
+            name,
+            generics,
+            where_clause,
+            fields: parse_fields(fields_stream),
+            options,
+        }
+    }
+
+    /// The `impl<...> Name<...> where ...` header for the inherent impls of the struct.
+    pub fn impl_header(&self) -> String {
+        self.trait_impl_header("")
+    }
+
+    /// The `impl<...> Trait for Name<...> where ...` header for the `trait_for` such as `Deref for `.
+    pub fn trait_impl_header(&self, trait_for: &str) -> String {
+        if self.generics.is_empty() {
+            return format!(
+                "impl {}{} {}",
+                trait_for,
+                self.name,
+                tokens_to_string(&self.where_clause)
+            );
+        }
+        let generic_args: Vec<String> = split_top_level(&self.generics)
+            .into_iter()
+            .map(|param| match param {
+                [TokenTree::Punct(punct), TokenTree::Ident(lifetime), ..]
+                    if punct.as_char() == '\'' =>
+                {
+                    format!("'{}", lifetime)
+                }
+                [TokenTree::Ident(const_kw), TokenTree::Ident(id), ..]
+                    if const_kw.to_string() == "const" =>
+                {
+                    id.to_string()
+                }
+                [TokenTree::Ident(id), ..] => id.to_string(),
+                others => panic!("Unexpected generic parameter: {:?}", others),
+            })
+            .collect();
+        format!(
+            "impl<{}> {}{}<{}> {}",
+            tokens_to_string(&self.generics),
+            trait_for,
+            self.name,
+            generic_args.join(", "),
+            tokens_to_string(&self.where_clause)
+        )
+    }
+
+    pub fn fields_enum_name(&self) -> String {
+        format!("{}Field", self.name)
+    }
+
+    pub fn path_struct_name(&self) -> String {
+        format!("{}Path", self.name)
+    }
+
+    /// The path options of the generated path constants from the container attributes such as `case`.
+    fn path_options(&self) -> HashMap<String, String> {
+        self.options
+            .iter()
+            .filter(|(option_name, _)| option_name.as_str() == "case")
+            .map(|(option_name, option_value)| (option_name.clone(), option_value.clone()))
+            .collect()
+    }
+
+    pub fn unwrapped_struct_name(&self) -> String {
+        format!("{}Unwrapped", self.name)
+    }
+
+    /// The wrapped field of the transparent newtype, skipping the `PhantomData` markers.
+    fn transparent_field(&self) -> &DeriveField {
+        let wrapped_fields: Vec<&DeriveField> = self
+            .fields
+            .iter()
+            .filter(|field| last_type_segment(&field.field_type).0 != "PhantomData")
+            .collect();
+        match wrapped_fields.as_slice() {
+            [wrapped_field] => wrapped_field,
+            _ => panic!(
+                "Transparent {} must have a single wrapped field, use #[struct_path(transparent = false)] to skip inlining it",
+                self.name
+            ),
+        }
+    }
+
+    fn is_option_enabled(&self, option_name: &str) -> bool {
+        match self.options.get(option_name).map(|s| s.as_str()) {
+            Some("true") => true,
+            Some("false") | None => false,
+            Some(others) => panic!(
+                "Unknown {} attribute value is specified: {}",
+                option_name, others
+            ),
+        }
+    }
+}
+
+impl DeriveField {
+    pub fn path_name(&self) -> &str {
+        self.name.strip_prefix("r#").unwrap_or(&self.name)
+    }
+
+    /// The JSON type name of the field, unwrapping `Option` and mapping the unknown types to `object`.
+    pub fn json_type(&self) -> &'static str {
+        json_type_of(&self.field_type)
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        last_type_segment(&self.field_type).0 == "Option"
+    }
+
+    /// The declared byte width of the field from `#[struct_path(width = 8)]` for the fixed-width formats.
+    pub fn width(&self) -> Option<usize> {
+        self.options.get("width").map(|width| {
+            width
+                .parse()
+                .unwrap_or_else(|_| panic!("Unknown width attribute value is specified: {}", width))
+        })
+    }
+
+    /// The `<Struct>Path` of the nested field type from `#[struct_path(nested)]`, unwrapping `Option`
+    /// and skipping the generic arguments.
+    pub fn nested_path_struct_name(&self) -> Option<String> {
+        if !self.options.contains_key("nested") {
+            return None;
+        }
+        let nested_type = self.nested_type()?;
+        let type_name_tokens: Vec<TokenTree> = nested_type
+            .iter()
+            .take_while(|token_tree| !matches!(token_tree, TokenTree::Punct(punct) if punct.as_char() == '<'))
+            .cloned()
+            .collect();
+        Some(format!("{}Path", tokens_to_string(&type_name_tokens)))
+    }
+
+    /// The nested field type from `#[struct_path(nested)]` with the `Option` and `Box` layers stripped.
+    pub fn nested_type(&self) -> Option<Vec<TokenTree>> {
+        if !self.options.contains_key("nested") {
+            return None;
+        }
+        match last_type_segment(&self.unwrapped_type()) {
+            (type_name, type_args) if type_name == "Box" => Some(type_args),
+            _ => Some(self.unwrapped_type()),
+        }
+    }
+
+    /// The field type with a single `Option` layer stripped.
+    pub fn unwrapped_type(&self) -> Vec<TokenTree> {
+        match last_type_segment(&self.field_type) {
+            (type_name, type_args) if type_name == "Option" => type_args,
+            _ => self.field_type.clone(),
+        }
+    }
+}
+
+fn json_type_of(field_type: &[TokenTree]) -> &'static str {
+    match field_type {
+        [TokenTree::Punct(punct), rest @ ..] if punct.as_char() == '&' => {
+            json_type_of(match rest {
+                [TokenTree::Punct(lifetime_punct), TokenTree::Ident(_), rest @ ..]
+                    if lifetime_punct.as_char() == '\'' =>
+                {
+                    rest
+                }
+                rest => rest,
+            })
+        }
+        [TokenTree::Ident(mut_kw), rest @ ..] if mut_kw.to_string() == "mut" => json_type_of(rest),
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::Bracket => "array",
+        _ => {
+            let (type_name, type_args) = last_type_segment(field_type);
+            match type_name.as_str() {
+                "Option" => json_type_of(&type_args),
+                "Box" | "Rc" | "Arc" | "Cow" => json_type_of(&type_args),
+                "String" | "str" | "char" => "string",
+                "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64"
+                | "i128" | "isize" | "f32" | "f64" => "number",
+                "bool" => "boolean",
+                "Vec" | "VecDeque" | "LinkedList" | "HashSet" | "BTreeSet" => "array",
+                _ => "object",
+            }
+        }
+    }
+}
+
+/// Returns the last path segment name of the type and the tokens of its first generic argument,
+/// so `std::option::Option<Box<u64>>` gives `("Option", [Box < u64 >])`.
+fn last_type_segment(field_type: &[TokenTree]) -> (String, Vec<TokenTree>) {
+    let mut type_name = String::new();
+    let mut type_args = Vec::new();
+    let mut type_tokens = field_type.iter();
+    while let Some(token_tree) = type_tokens.next() {
+        match token_tree {
+            TokenTree::Ident(id) => type_name = id.to_string(),
+            TokenTree::Punct(punct) if punct.as_char() == '<' => {
+                let mut angle_brackets_counter = 1;
+                let mut args_tokens = Vec::new();
+                for token_tree in type_tokens.by_ref() {
+                    if let TokenTree::Punct(punct) = token_tree {
+                        match punct.as_char() {
+                            '<' => angle_brackets_counter += 1,
+                            '>' => angle_brackets_counter -= 1,
+                            _ => {}
+                        }
+                        if angle_brackets_counter == 0 {
+                            break;
+                        }
+                    }
+                    args_tokens.push(token_tree.clone());
+                }
+                type_args = split_top_level(&args_tokens)
+                    .first()
+                    .map(|arg| arg.to_vec())
+                    .unwrap_or_default();
+            }
+            _ => {}
+        }
+    }
+    (type_name, type_args)
+}
+
+pub fn split_top_level(tokens: &[TokenTree]) -> Vec<&[TokenTree]> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut comma_positions = top_level_comma_positions(tokens);
+    comma_positions.push(tokens.len());
+    for end in comma_positions {
+        if start < end {
+            parts.push(&tokens[start..end]);
+        }
+        start = end + 1;
+    }
+    parts
+}
+
+fn tokens_to_string(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
+
+pub fn field_variant_name(field_name: &str) -> String {
+    field_name.from_case(Case::Snake).to_case(Case::Pascal)
+}
+
+pub fn derive_struct_path(derive_stream: TokenStream) -> TokenStream {
+    let derive_struct = DeriveStruct::parse(derive_stream);
+    let fields_enum_name = derive_struct.fields_enum_name();
+
+    let fields_enum_code = format!(
+        r#"
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            {} enum {} {{
+                {}
+            }}
+
+            impl {} {{
+                pub fn path(&self) -> &'static str {{
+                    match *self {{
+                        {}
+                    }}
+                }}
+
+                pub const fn index(&self) -> usize {{
+                    match *self {{
+                        {}
+                    }}
+                }}
+            }}
+
+            {} {{
+                pub const JSON_TYPES: &'static [(&'static str, &'static str)] = &[{}];
+                pub const NULLABLE: &'static [(&'static str, bool)] = &[{}];
+                pub const WIDTHS: &'static [(&'static str, usize)] = &[{}];
+                pub const CUSTOM_SERDE: &'static [&'static str] = &[{}];
+            }}
+        "#,
+        derive_struct.vis,
+        fields_enum_name,
+        derive_struct
+            .fields
+            .iter()
+            .map(|field| field_variant_name(field.path_name()))
+            .collect::<Vec<String>>()
+            .join(",\n"),
+        fields_enum_name,
+        derive_struct
+            .fields
+            .iter()
+            .map(|field| format!(
+                "{}::{} => \"{}\"",
+                fields_enum_name,
+                field_variant_name(field.path_name()),
+                field.path_name()
+            ))
+            .collect::<Vec<String>>()
+            .join(",\n"),
+        derive_struct
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| format!(
+                "{}::{} => {}",
+                fields_enum_name,
+                field_variant_name(field.path_name()),
+                index
+            ))
+            .collect::<Vec<String>>()
+            .join(",\n"),
+        derive_struct.impl_header(),
+        derive_struct
+            .fields
+            .iter()
+            .map(|field| format!("(\"{}\", \"{}\")", field.path_name(), field.json_type()))
+            .collect::<Vec<String>>()
+            .join(", "),
+        derive_struct
+            .fields
+            .iter()
+            .map(|field| format!("(\"{}\", {})", field.path_name(), field.is_nullable()))
+            .collect::<Vec<String>>()
+            .join(", "),
+        derive_struct
+            .fields
+            .iter()
+            .filter_map(|field| field.width().map(|width| format!(
+                "(\"{}\", {})",
+                field.path_name(),
+                width
+            )))
+            .collect::<Vec<String>>()
+            .join(", "),
+        derive_struct
+            .fields
+            .iter()
+            .filter(|field| field.options.contains_key("custom_serde"))
+            .map(|field| format!("\"{}\"", field.path_name()))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+
+    let unwrapped_struct_code = if derive_struct.is_option_enabled("unwrap_options") {
+        let generics = if derive_struct.generics.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", tokens_to_string(&derive_struct.generics))
+        };
+        format!(
+            r#"
+            #[allow(dead_code)]
+            {} struct {}{} {} {{
+                {}
+            }}
+            "#,
+            derive_struct.vis,
+            derive_struct.unwrapped_struct_name(),
+            generics,
+            tokens_to_string(&derive_struct.where_clause),
+            derive_struct
+                .fields
+                .iter()
+                .map(|field| format!(
+                    "{} {}: {}",
+                    field.vis,
+                    field.name,
+                    tokens_to_string(&field.unwrapped_type())
+                ))
+                .collect::<Vec<String>>()
+                .join(",\n")
+        )
+    } else {
+        String::new()
+    };
+
+    let match_paths_code = if derive_struct.is_option_enabled("match_paths") {
+        format!(
+            r#"
+            {} {{
+                pub fn match_path(&self, path: &str) -> Option<&dyn ::core::fmt::Debug> {{
+                    match path {{
+                        {}
+                        _ => None,
+                    }}
+                }}
+            }}
+            "#,
+            derive_struct.impl_header(),
+            derive_struct
+                .fields
+                .iter()
+                .map(|field| format!(
+                    "\"{}\" => Some(&self.{} as &dyn ::core::fmt::Debug),",
+                    field.path_name(),
+                    field.name
+                ))
+                .collect::<Vec<String>>()
+                .join("\n")
+        )
+    } else {
+        String::new()
+    };
+
+    let path_options = derive_struct.path_options();
+    // The transparent newtypes are serialized as the wrapped values, so the paths of
+    // the wrapped `#[struct_path(nested)]` type are inlined without the wrapper field segment,
+    // while the other wrappers are the leaves themselves
+    let transparent_field = if derive_struct.is_option_enabled("transparent") {
+        Some(derive_struct.transparent_field())
+    } else {
+        None
+    };
+    let path_struct_code = match transparent_field {
+        Some(transparent_field) => match transparent_field.nested_path_struct_name() {
+            Some(nested_path_struct_name) => format!(
+                "{} type {} = {};",
+                derive_struct.vis,
+                derive_struct.path_struct_name(),
+                nested_path_struct_name
+            ),
+            None => format!(
+                r#"
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+                {} struct {};
+                "#,
+                derive_struct.vis,
+                derive_struct.path_struct_name()
+            ),
+        },
+        None => format!(
+            r#"
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        {} struct {};
+
+        #[allow(dead_code)]
+        impl {} {{
+            {}
+            {}
+        }}
+        "#,
+            derive_struct.vis,
+            derive_struct.path_struct_name(),
+            derive_struct.path_struct_name(),
+            derive_struct
+                .fields
+                .iter()
+                .map(|field| format!(
+                    "pub const {}: &'static str = {:?};",
+                    field.path_name().to_uppercase(),
+                    apply_options(&path_options, field.path_name().to_string())
+                ))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            derive_struct
+                .fields
+                .iter()
+                .filter_map(|field| field.nested_path_struct_name().map(
+                    |nested_path_struct_name| {
+                        if !cfg!(feature = "std") {
+                            panic!(
+                                "The nested paths of {} require the std feature",
+                                field.path_name()
+                            );
+                        }
+                        format!(
+                            r#"pub fn {}(nested_path: &str) -> ::std::string::String {{
+                        let _ = ::core::marker::PhantomData::<{}>;
+                        ::std::format!("{{}}.{{}}", Self::{}, nested_path)
+                    }}"#,
+                            field.name,
+                            nested_path_struct_name,
+                            field.path_name().to_uppercase()
+                        )
+                    }
+                ))
+                .collect::<Vec<String>>()
+                .join("\n")
+        ),
+    };
+
+    // The nested types are only visited once on the way down, so the cycles through
+    // `Option<Box<Self>>` and alike end with the field path of the revisited type as a leaf.
+    // The transparent newtypes delegate to the wrapped type with the same prefix
+    let all_paths_code = if cfg!(feature = "std") {
+        format!(
+            r#"
+            {} {{
+                /// Returns all the leaf paths recursing into the `#[struct_path(nested)]` fields
+                /// and the wrapped fields of the transparent newtypes. The other fields are the leaves
+                /// even when their types derive `StructPath`.
+                pub fn all_paths() -> ::std::vec::Vec<::std::string::String> {{
+                    let mut all_paths = ::std::vec::Vec::new();
+                    Self::collect_leaf_paths("", &mut ::std::vec::Vec::new(), &mut all_paths);
+                    all_paths
+                }}
+
+                #[doc(hidden)]
+                pub fn collect_leaf_paths(
+                    prefix: &str,
+                    visited_types: &mut ::std::vec::Vec<&'static str>,
+                    all_paths: &mut ::std::vec::Vec<::std::string::String>,
+                ) {{
+                    let type_name = ::core::any::type_name::<Self>();
+                    if visited_types.contains(&type_name) {{
+                        all_paths.push(prefix.trim_end_matches('.').to_string());
+                        return;
+                    }}
+                    visited_types.push(type_name);
+                    {}
+                    visited_types.pop();
+                }}
+            }}
+            "#,
+            derive_struct.impl_header(),
+            match transparent_field {
+                Some(transparent_field) => match transparent_field.nested_type() {
+                    Some(nested_type) => format!(
+                        "<{}>::collect_leaf_paths(prefix, visited_types, all_paths);",
+                        tokens_to_string(&nested_type)
+                    ),
+                    None => "all_paths.push(prefix.trim_end_matches('.').to_string());".to_string(),
+                },
+                None => derive_struct
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let path = apply_options(&path_options, field.path_name().to_string());
+                        match field.nested_type() {
+                            Some(nested_type) => format!(
+                                "<{}>::collect_leaf_paths(&::std::format!(\"{{}}{}.\", prefix), visited_types, all_paths);",
+                                tokens_to_string(&nested_type),
+                                path
+                            ),
+                            None => format!(
+                                "all_paths.push(::std::format!(\"{{}}{}\", prefix));",
+                                path
+                            ),
+                        }
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            }
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        "{}{}{}{}{}",
+        fields_enum_code, unwrapped_struct_code, match_paths_code, path_struct_code, all_paths_code
+    )
+    .parse()
+    .unwrap()
+}
+
+/// Parses the `#[struct_path(...)]` container and field attributes such as `#[struct_path(unwrap_options)]`,
+/// `#[struct_path(unwrap_options = false)]` or `#[struct_path(width = 8)]`, skipping all the other attributes
+/// except `#[serde(transparent)]` and `#[repr(transparent)]` enabling the `transparent` option by default
+/// and `#[serde(with = "...")]` (or `serialize_with` and `deserialize_with`) marking the `custom_serde` fields.
+fn parse_struct_path_attributes(
+    derive_tokens: &mut std::iter::Peekable<impl Iterator<Item = TokenTree>>,
+) -> HashMap<String, String> {
+    let mut options = HashMap::new();
+    while matches!(derive_tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '#') {
+        derive_tokens.next();
+        let attribute_tokens: Vec<TokenTree> = match derive_tokens.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => {
+                group.stream().into_iter().collect()
+            }
+            others => panic!("Unexpected attribute format: {:?}", others),
+        };
+        if let [TokenTree::Ident(attribute_name), TokenTree::Group(attribute_group)] =
+            attribute_tokens.as_slice()
+        {
+            if attribute_name.to_string() == "struct_path" {
+                let option_tokens: Vec<TokenTree> = attribute_group.stream().into_iter().collect();
+                for option_definition in split_top_level(&option_tokens) {
+                    match option_definition {
+                        [TokenTree::Ident(option_name)] => {
+                            options.insert(option_name.to_string(), "true".to_string());
+                        }
+                        [TokenTree::Ident(option_name), TokenTree::Punct(eq), option_value]
+                            if eq.as_char() == '=' =>
+                        {
+                            let option_value = option_value.to_string();
+                            options.insert(
+                                option_name.to_string(),
+                                string_literal_value(&option_value).unwrap_or(option_value),
+                            );
+                        }
+                        others => panic!("Unexpected struct_path attribute format: {:?}", others),
+                    }
+                }
+            } else if attribute_name.to_string() == "serde"
+                && split_top_level(&attribute_group.stream().into_iter().collect::<Vec<TokenTree>>())
+                    .into_iter()
+                    .any(|serde_option| matches!(serde_option, [TokenTree::Ident(id), ..] if ["with", "serialize_with", "deserialize_with"].contains(&id.to_string().as_str())))
+            {
+                options.insert("custom_serde".to_string(), "true".to_string());
+            } else if (attribute_name.to_string() == "serde" || attribute_name.to_string() == "repr")
+                && attribute_group.stream().into_iter().any(
+                    |token_tree| matches!(token_tree, TokenTree::Ident(id) if id.to_string() == "transparent"),
+                )
+            {
+                options
+                    .entry("transparent".to_string())
+                    .or_insert_with(|| "true".to_string());
+            }
+        }
+    }
+    options
+}
+
+fn parse_visibility(
+    derive_tokens: &mut std::iter::Peekable<impl Iterator<Item = TokenTree>>,
+) -> String {
+    match derive_tokens.peek() {
+        Some(TokenTree::Ident(id)) if id.to_string() == "pub" => {
+            let mut vis = derive_tokens.next().unwrap().to_string();
+            if let Some(TokenTree::Group(group)) = derive_tokens.peek() {
+                if group.delimiter() == Delimiter::Parenthesis {
+                    vis.push_str(&derive_tokens.next().unwrap().to_string());
+                }
+            }
+            vis
+        }
+        _ => String::new(),
+    }
+}
+
+fn parse_fields(fields_stream: TokenStream) -> Vec<DeriveField> {
+    let fields_tokens: Vec<TokenTree> = fields_stream.into_iter().collect();
+    let mut fields = Vec::new();
+
+    for field_definition in split_top_level(&fields_tokens) {
+        let mut field_tokens = field_definition.iter().cloned().peekable();
+        let options = parse_struct_path_attributes(&mut field_tokens);
+        let vis = parse_visibility(&mut field_tokens);
+        let name = match field_tokens.next() {
+            Some(TokenTree::Ident(id)) => id.to_string(),
+            others => panic!("Unexpected struct field name: {:?}", others),
+        };
+        match field_tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {}
+            others => panic!("Unexpected struct field definition: {:?}", others),
+        }
+        fields.push(DeriveField {
+            vis,
+            name,
+            field_type: field_tokens.collect(),
+            options,
+        });
+    }
+    fields
+}
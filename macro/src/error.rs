@@ -0,0 +1,58 @@
+//! The errors of the malformed macro invocations reported as `compile_error!` at the offending tokens,
+//! so rustc shows them as the usual diagnostics instead of the proc-macro panics.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+
+pub struct PathError {
+    span: Span,
+    message: String,
+    segment: Option<String>,
+}
+
+impl PathError {
+    pub fn new(span: Span, message: String) -> Self {
+        PathError {
+            span,
+            message,
+            segment: None,
+        }
+    }
+
+    /// The error of a field path segment such as `value_str` or `"literal"`,
+    /// spanned at the segment token with `spanned_at`.
+    pub fn at_segment(segment: &str, message: String) -> Self {
+        PathError {
+            span: Span::call_site(),
+            message,
+            segment: Some(segment.to_string()),
+        }
+    }
+
+    pub fn call_site(message: String) -> Self {
+        PathError::new(Span::call_site(), message)
+    }
+
+    /// Moves the error of a segment to the segment token of the field path spans.
+    pub fn spanned_at(mut self, path_spans: &[(String, Span)]) -> Self {
+        if let Some(segment) = &self.segment {
+            if let Some((_, span)) = path_spans
+                .iter()
+                .find(|(token_name, _)| token_name == segment)
+            {
+                self.span = *span;
+            }
+        }
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns `::core::compile_error!("...")` spanned at the error location.
+    pub fn to_compile_error(&self) -> TokenStream {
+        let message = &self.message;
+        quote_spanned!(self.span=> ::core::compile_error!(#message))
+    }
+}
@@ -9,6 +9,9 @@
 //! - Multiple fields/arrays support
 //! - Optional camelCase and PascalCase conversion support;
 //! - Optional delimiter parameter;
+//!
+//! The path syntax and the options are described on [`path!`], the generated items on [`StructPath`](derive@StructPath).
+//!
 //! Example:
 //!
 //! ```rust,no_run
@@ -48,437 +51,3542 @@
 //!
 
 use convert_case::{Case, Casing};
-use proc_macro::{TokenStream, TokenTree};
+use proc_macro::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+use quote::quote;
 use std::collections::HashMap;
 
+mod segment_pattern;
+use segment_pattern::SegmentPattern;
+
+mod selection_set;
+use selection_set::SelectionSet;
+
+mod derive;
+
+mod error;
+use error::PathError;
+
+mod parser;
+use parser::{
+    string_literal_value, try_parse_path, try_parse_paths, FieldSpans, ParsedPath, ParsedPaths,
+};
+
+/// Returns the array of the paths for the struct fields, see `path!` for the syntax and the options.
+/// With `format = "graphql"` returns a single GraphQL selection set with the shared prefixes merged instead,
+/// so `paths!(TestStructParent::{ value_str, value_child.child_value_str }; format = "graphql")`
+/// returns `"value_str value_child { child_value_str }"`.
+///
+/// The malformed invocations are reported as the compile errors at the offending tokens:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// let s: [&str; 1] = paths!(TestStructParent::{ value_str }, ,);
+/// ```
 #[proc_macro]
 pub fn paths(struct_path_stream: TokenStream) -> TokenStream {
-    let mut current_struct_name: Option<String> = None;
-    let mut current_struct_fields: Vec<String> = Vec::with_capacity(16);
+    let parsed_paths = match parse_checked_paths(struct_path_stream) {
+        Ok(parsed_paths) => parsed_paths,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions = generate_checks_for(
+        &parsed_paths.found_structs,
+        &parsed_paths.field_spans,
+        &parsed_paths.options,
+    );
+    let result_stream = if parsed_paths.options.get("format").map(|s| s.as_str()) == Some("graphql")
+    {
+        let selection_set = final_selection_set(&parsed_paths).to_string();
+        quote! {{ #all_check_functions #selection_set }}
+    } else {
+        let all_final_fields = final_field_paths(&parsed_paths);
+        quote! {{ #all_check_functions [#(#all_final_fields),*] }}
+    };
+    result_stream.into()
+}
 
-    let mut opened_struct = false;
-    let mut colons_counter = 0;
-    let mut options_opened = false;
+/// Returns a JSON array of the paths as a string literal, so
+/// `paths_json!(TestStructParent::{ value_str, value_num })` returns `"[\"value_str\",\"value_num\"]"`.
+/// The paths with the `reserved` keys are rejected as in `paths!`:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructDocument {
+///     pub _id: String,
+///     pub value_str: String,
+/// }
+///
+/// let s: &str = paths_json!(TestStructDocument::{ _id, value_str }; reserved = "_id");
+/// ```
+#[proc_macro]
+pub fn paths_json(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_paths = match parse_checked_paths(struct_path_stream) {
+        Ok(parsed_paths) => parsed_paths,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_paths.found_structs, &parsed_paths.options);
+    let json_array = format!(
+        "[{}]",
+        final_field_paths(&parsed_paths)
+            .iter()
+            .map(|final_field_path| json_escape(final_field_path))
+            .collect::<Vec<String>>()
+            .join(",")
+    );
 
-    let mut current_field_path: Option<String> = None;
+    format!("{{{}\n{:?}}}", all_check_functions, json_array)
+        .parse()
+        .unwrap()
+}
 
-    let mut current_option_name: Option<String> = None;
-    let mut expect_option_value: bool = false;
+/// Returns the comma separated paths as a single string literal for the protobuf `FieldMask`s, so
+/// `field_mask!(TestStructParent::{ value_str, value_child.child_value_str }; case = "camel")`
+/// returns `"valueStr,valueChild.childValueStr"`. The segments violating `segment_pattern` are rejected
+/// as in `path!`:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// let s: &str = field_mask!(TestStructParent::value_str; segment_pattern = "[a-z]+");
+/// ```
+///
+/// The empty segments are rejected as well unless `on_empty_segment` allows them:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// let s: &str = field_mask!(TestStructParent::value_str; abbrev = "value_str=");
+/// ```
+#[proc_macro]
+pub fn field_mask(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_paths = match parse_checked_paths(struct_path_stream) {
+        Ok(parsed_paths) => parsed_paths,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_paths.found_structs, &parsed_paths.options);
+    let field_mask = final_field_paths(&parsed_paths).join(",");
 
-    let mut options: HashMap<String, String> = HashMap::new();
-    let mut found_structs: Vec<(String, Vec<String>)> = Vec::new();
+    format!("{{{}\n{:?}}}", all_check_functions, field_mask)
+        .parse()
+        .unwrap()
+}
 
-    for token_tree in struct_path_stream.into_iter() {
-        match token_tree {
-            TokenTree::Ident(id) if current_struct_name.is_none() => {
-                current_struct_name = Some(id.to_string());
-            }
-            TokenTree::Punct(punct)
-                if current_struct_name.is_some()
-                    && !opened_struct
-                    && punct == ':'
-                    && colons_counter < 2 =>
-            {
-                colons_counter += 1;
-                if colons_counter > 1 {
-                    opened_struct = true;
-                }
-            }
-            TokenTree::Ident(id) if opened_struct => {
-                colons_counter = 0;
-                if let Some(ref mut field_path) = &mut current_field_path {
-                    field_path.push_str(id.to_string().as_str())
-                } else {
-                    current_field_path = Some(id.to_string());
-                }
-            }
-            TokenTree::Punct(punct)
-                if current_struct_name.is_some()
-                    && opened_struct
-                    && punct == ':'
-                    && colons_counter < 2 =>
-            {
-                colons_counter += 1;
-                opened_struct = false;
-                if let Some(ref mut field_path) = current_field_path.take() {
-                    if let Some(ref mut struct_name) = &mut current_struct_name {
-                        struct_name.push_str("::");
-                        struct_name.push_str(field_path);
-                    }
-                }
-            }
-            TokenTree::Punct(punct) if opened_struct && (punct == '.' || punct == '~') => {
-                if let Some(ref mut field_path) = &mut current_field_path {
-                    field_path.push(punct.as_char());
-                } else {
-                    panic!(
-                        "Unexpected punctuation input for struct path group parameters: {:?}",
-                        punct
-                    )
-                }
-            }
-            TokenTree::Group(group) if opened_struct && current_field_path.is_none() => {
-                parse_multiple_fields(group.stream(), &mut current_struct_fields)
-            }
-            TokenTree::Punct(punct) if !options_opened && opened_struct && punct == ',' => {
-                opened_struct = false;
-                colons_counter = 0;
-                if let Some(struct_name) = current_struct_name.take() {
-                    if let Some(field_path) = current_field_path.take() {
-                        current_struct_fields.push(field_path);
-                    }
-                    if !current_struct_fields.is_empty() {
-                        found_structs
-                            .push((struct_name, current_struct_fields.drain(..).collect()));
-                    } else {
-                        panic!("Unexpected comma with empty fields for {}!", struct_name);
-                    }
-                } else {
-                    panic!("Unexpected comma with empty definitions!");
-                }
-            }
-            TokenTree::Punct(punct) if punct == ';' && opened_struct && !options_opened => {
-                options_opened = true;
-                opened_struct = false;
-            }
-            TokenTree::Ident(id) if options_opened && !expect_option_value => {
-                current_option_name = Some(id.to_string())
-            }
-            TokenTree::Ident(id) if options_opened && expect_option_value => {
-                expect_option_value = false;
-                match current_option_name.take() {
-                    Some(option_name) => {
-                        options.insert(option_name, id.to_string());
-                    }
-                    _ => {
-                        panic!("Wrong options format")
-                    }
-                }
+/// Returns a `phf::Map<&str, usize>` from the paths to their indices, so
+/// `phf_paths!(TestStructParent::{ value_str, value_num }; case = "camel")` returns
+/// the map with `"valueStr" => 0` and `"valueNum" => 1` for the zero-cost lookups of the serialized names.
+/// Available with the `phf` feature and requires the `phf` crate with the `macros` feature.
+#[cfg(feature = "phf")]
+#[proc_macro]
+pub fn phf_paths(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_paths = match parse_checked_paths(struct_path_stream) {
+        Ok(parsed_paths) => parsed_paths,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_paths.found_structs, &parsed_paths.options);
+    let all_map_entries = final_field_paths(&parsed_paths)
+        .iter()
+        .enumerate()
+        .map(|(index, final_field_path)| format!("{:?} => {}usize", final_field_path, index))
+        .collect::<Vec<String>>();
+
+    format!(
+        "{{{}\n::phf::phf_map! {{ {} }}}}",
+        all_check_functions,
+        all_map_entries.join(",")
+    )
+    .parse()
+    .unwrap()
+}
+
+/// Returns a `BTreeMap` from the original field paths to the paths with the options applied, so
+/// `paths_btree!(TestStructParent::{ value_str, value_num }; case = "camel")` returns
+/// `{"value_num": "valueNum", "value_str": "valueStr"}` for building the ordered rename tables.
+/// Available with the `std` feature (enabled by default).
+#[cfg(feature = "std")]
+#[proc_macro]
+pub fn paths_btree(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_paths = match parse_checked_paths(struct_path_stream) {
+        Ok(parsed_paths) => parsed_paths,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_paths.found_structs, &parsed_paths.options);
+    let original_options = HashMap::new();
+    let all_entries = parsed_paths
+        .found_structs
+        .iter()
+        .flat_map(|(_, struct_fields)| struct_fields.iter())
+        .zip(final_field_paths(&parsed_paths))
+        .map(|(field_path, final_field_path)| {
+            format!(
+                "({:?}, {:?})",
+                apply_options(&original_options, field_path.clone()),
+                final_field_path
+            )
+        })
+        .collect::<Vec<String>>();
+
+    format!(
+        "{{{}\n::std::collections::BTreeMap::<&'static str, &'static str>::from([{}])}}",
+        all_check_functions,
+        all_entries.join(",")
+    )
+    .parse()
+    .unwrap()
+}
+
+/// Returns a `HashMap` from the last field names to the paths with the options applied, so
+/// `paths_map!(TestStructParent::{ value_str, value_child.child_value_str }; case = "camel")` returns
+/// `{"value_str": "valueStr", "child_value_str": "valueChild.childValueStr"}` for the diagnostics.
+/// Available with the `std` feature (enabled by default).
+///
+/// The paths sharing the last field name are rejected, since one of them would be lost in the map:
+///
+/// ```rust,compile_fail
+/// use struct_path::paths_map;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+///     pub value_child: TestStructChild,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub value_str: String,
+/// }
+///
+/// let _ = paths_map!(TestStructParent::{ value_str, value_child.value_str });
+/// ```
+#[cfg(feature = "std")]
+#[proc_macro]
+pub fn paths_map(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_paths = match parse_checked_paths(struct_path_stream) {
+        Ok(parsed_paths) => parsed_paths,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_paths.found_structs, &parsed_paths.options);
+    let mut all_keys: HashMap<String, &String> = HashMap::new();
+    let mut all_final_fields = final_field_paths(&parsed_paths).into_iter();
+    let mut all_entries: Vec<String> = Vec::with_capacity(16);
+    for (struct_pos, (_, struct_fields)) in parsed_paths.found_structs.iter().enumerate() {
+        for (field_pos, field_path) in struct_fields.iter().enumerate() {
+            let key = last_field_name(field_path);
+            if let Some(another_field_path) = all_keys.insert(key.clone(), field_path) {
+                // The collision is reported at the last field token of the second path
+                let span = parsed_paths
+                    .field_spans
+                    .get(struct_pos)
+                    .and_then(|struct_field_spans| struct_field_spans.get(field_pos))
+                    .and_then(|path_spans| path_spans.last())
+                    .map(|(_, span)| *span)
+                    .unwrap_or_else(proc_macro2::Span::call_site);
+                return PathError::new(
+                    span,
+                    format!(
+                        "Paths {} and {} share the same last field name: {}",
+                        another_field_path, field_path, key
+                    ),
+                )
+                .to_compile_error()
+                .into();
             }
-            TokenTree::Literal(lit) if options_opened && expect_option_value => {
-                expect_option_value = false;
-                match current_option_name.take() {
-                    Some(option_name) => {
-                        let lit_str = lit.to_string();
-                        options.insert(
-                            option_name,
-                            lit_str.as_str()[1..lit_str.len() - 1].to_string(),
-                        );
-                    }
-                    _ => {
-                        panic!("Wrong options format")
-                    }
+            all_entries.push(format!(
+                "({:?}, {:?})",
+                key,
+                all_final_fields.next().unwrap()
+            ));
+        }
+    }
+
+    format!(
+        "{{{}\n::std::collections::HashMap::<&'static str, &'static str>::from([{}])}}",
+        all_check_functions,
+        all_entries.join(",")
+    )
+    .parse()
+    .unwrap()
+}
+
+/// Returns the reverse map from the paths with the options applied to the original field paths, so
+/// `rename_map!(TestStructParent::{ value_str, value_num }; case = "camel")` returns
+/// `[("valueStr", "value_str"), ("valueNum", "value_num")]` for the deserializers keyed by the serialized names.
+#[proc_macro]
+pub fn rename_map(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_paths = match parse_checked_paths(struct_path_stream) {
+        Ok(parsed_paths) => parsed_paths,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_paths.found_structs, &parsed_paths.options);
+    let original_options = HashMap::new();
+    let all_entries = parsed_paths
+        .found_structs
+        .iter()
+        .flat_map(|(_, struct_fields)| struct_fields.iter())
+        .zip(final_field_paths(&parsed_paths))
+        .map(|(field_path, final_field_path)| {
+            format!(
+                "({:?}, {:?})",
+                final_field_path,
+                apply_options(&original_options, field_path.clone())
+            )
+        })
+        .collect::<Vec<String>>();
+
+    format!("{{{}\n[{}]}}", all_check_functions, all_entries.join(","))
+        .parse()
+        .unwrap()
+}
+
+/// Returns the paths grouped by their structs, so
+/// `paths_grouped!(TestStructParent::{ value_str, value_num }, TestStructChild::{ child_value_str })` returns
+/// `[("TestStructParent", &["value_str", "value_num"]), ("TestStructChild", &["child_value_str"])]`.
+/// The structs are represented by their simple names.
+#[proc_macro]
+pub fn paths_grouped(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_paths = match parse_checked_paths(struct_path_stream) {
+        Ok(parsed_paths) => parsed_paths,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_paths.found_structs, &parsed_paths.options);
+    let all_groups = parsed_paths
+        .found_structs
+        .iter()
+        .map(|(struct_name, struct_fields)| {
+            format!(
+                "({:?}, &[{}] as &[&str])",
+                simple_struct_name(struct_name),
+                struct_fields
+                    .iter()
+                    .map(|field_path| format!(
+                        "{:?}",
+                        final_struct_field_path(&parsed_paths.options, struct_name, field_path)
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            )
+        })
+        .collect::<Vec<String>>();
+
+    format!("{{{}\n[{}]}}", all_check_functions, all_groups.join(","))
+        .parse()
+        .unwrap()
+}
+
+/// Derives the `<Struct>Field` enum with a variant for every struct field,
+/// so `#[derive(StructPath)] struct TestStructChild { child_value_str: String }` generates
+/// `TestStructChildField::ChildValueStr` with `TestStructChildField::ChildValueStr.path() == "child_value_str"`
+/// and the `TestStructChildPath` unit struct with the `TestStructChildPath::CHILD_VALUE_STR` path constant.
+///
+/// The `<Struct>Field` enum is used with `field_of!` and `path_with_index!`, with the field declaration indices
+/// from `<Struct>Field::index`. The path constants are converted with the container attribute
+/// `#[struct_path(case = "camel")]`, and the fields marked with `#[struct_path(nested)]` (deriving `StructPath` as well)
+/// get the `<Struct>Path` methods prefixing the nested paths (requires the `std` feature), so
+/// `TestStructNestedPath::value_child(TestStructNestedChildPath::CHILD_VALUE_STR)` returns `valueChild.childValueStr`.
+///
+/// The derive also generates:
+/// - `<Struct>::JSON_TYPES` listing the field paths with their JSON types (`string`, `number`, `boolean`, `array`
+///   or `object` for the other types, `Option` is unwrapped) and `<Struct>::NULLABLE` marking the `Option` fields;
+/// - `<Struct>::CUSTOM_SERDE` listing the fields with the custom serde functions such as `#[serde(with = "...")]`;
+/// - `<Struct>::WIDTHS` listing the byte widths of the fields declared with `#[struct_path(width = 8)]`
+///   for the fixed-width formats (the fields without the attribute are omitted);
+/// - the `match_path` method returning the `Debug` field values by their paths for `match_paths!`
+///   with `#[struct_path(match_paths)]` on the struct;
+/// - the `<Struct>Unwrapped` struct with a single `Option` layer stripped from every field
+///   with `#[struct_path(unwrap_options)]` on the struct, so the nested paths of the builder structs are
+///   addressed directly as `path!(<Struct>Unwrapped::address.city)` instead of `path!(<Struct>::address~city)`.
+///
+/// With the `std` feature it also generates `<Struct>::all_paths()` returning all the leaf paths for the full field masks,
+/// recursing into the `#[struct_path(nested)]` fields (with `Option` and `Box` unwrapped), such as
+/// `["valueStr", "valueChild.childValueStr", "optValueChild.childValueStr"]` for `TestStructNested`.
+/// The other fields are the leaves even when their types derive `StructPath`. The nested types already visited
+/// on the way down aren't expanded again, so the recursive types such as `children: Option<Box<TestStructTree>>`
+/// end with the `children` leaf instead of the infinite paths.
+///
+/// The `#[serde(transparent)]` or `#[repr(transparent)]` newtypes inline the paths of their wrapped
+/// `#[struct_path(nested)]` field as serde does, so `<Newtype>Path` is the `<Wrapped>Path` of the field
+/// and `TestStructTransparentPath::owner(TestTransparentEntityPath::ID)` returns `owner.id` for
+/// `owner: TestTransparentEntity` wrapping `TestStructEntity { id }`. The newtypes wrapping the other fields
+/// are the leaves without the path constants. The inlining is turned off with `#[struct_path(transparent = false)]`.
+#[proc_macro_derive(StructPath, attributes(struct_path))]
+pub fn struct_path_derive(derive_stream: TokenStream) -> TokenStream {
+    derive::derive_struct_path(derive_stream)
+}
+
+/// Dispatches a runtime path to the field value of a struct deriving `StructPath`
+/// with `#[struct_path(match_paths)]`, so `match_paths!(test_struct: &TestStructChild, path_var)`
+/// returns `Some(&test_struct.child_value_str as &dyn Debug)` for `path_var == "child_value_str"`
+/// and `None` for the unknown paths. All the fields must implement `Debug`.
+#[proc_macro]
+pub fn match_paths(match_paths_stream: TokenStream) -> TokenStream {
+    let match_tokens: Vec<TokenTree> = match_paths_stream.into_iter().collect();
+    let comma_pos = match top_level_comma_positions(&match_tokens).as_slice() {
+        [comma_pos] => *comma_pos,
+        _ => panic!("Expected a typed struct value and a path separated by a comma!"),
+    };
+    let binding_tokens = &match_tokens[..comma_pos];
+    let colon_pos = binding_tokens
+        .iter()
+        .enumerate()
+        .position(|(pos, token_tree)| {
+            let is_single_colon = |token_tree: Option<&TokenTree>| {
+                matches!(token_tree, Some(TokenTree::Punct(punct)) if punct.as_char() == ':')
+            };
+            is_single_colon(Some(token_tree))
+                && !is_single_colon(binding_tokens.get(pos + 1))
+                && (pos == 0 || !is_single_colon(binding_tokens.get(pos - 1)))
+        })
+        .unwrap_or_else(|| panic!("Expected a struct value with its type such as `s: &User`!"));
+    let value_expr: TokenStream = binding_tokens[..colon_pos].iter().cloned().collect();
+    let value_type: TokenStream = binding_tokens[colon_pos + 1..].iter().cloned().collect();
+    let struct_type: TokenStream = match &binding_tokens[colon_pos + 1..] {
+        [TokenTree::Punct(ref_punct), struct_tokens @ ..] if ref_punct.as_char() == '&' => {
+            match struct_tokens {
+                [TokenTree::Punct(lifetime_punct), TokenTree::Ident(_), struct_tokens @ ..]
+                    if lifetime_punct.as_char() == '\'' =>
+                {
+                    struct_tokens.iter().cloned().collect()
                 }
-            }
-            TokenTree::Punct(punct) if options_opened && punct == '=' => {
-                expect_option_value = true;
-            }
-            TokenTree::Punct(punct) if options_opened && punct == ',' => {
-                expect_option_value = false;
-            }
-            others => {
-                panic!("Unexpected input for struct path parameters: {:?}", others)
+                struct_tokens => struct_tokens.iter().cloned().collect(),
             }
         }
+        _ => panic!("Expected a struct reference type such as `s: &User`!"),
+    };
+    let path_expr: TokenStream = match_tokens[comma_pos + 1..].iter().cloned().collect();
+
+    format!(
+        "{{ let _match_value: {} = {}; <{}>::match_path(_match_value, {}) }}",
+        value_type, value_expr, struct_type, path_expr
+    )
+    .parse()
+    .unwrap()
+}
+
+/// Returns the `<Struct>Field` enum variant generated by `#[derive(StructPath)]` for the field,
+/// so `field_of!(TestStructChild::child_value_str)` returns `TestStructChildField::ChildValueStr`.
+#[proc_macro]
+pub fn field_of(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    if parsed_path.found_structs.len() != 1 || split_field_path(&parsed_path.field_path).len() > 1 {
+        panic!(
+            "Expected a direct struct field for the fields enum: {}",
+            parsed_path.field_path
+        );
     }
+    let struct_name = struct_type_name(&parsed_path.found_structs[0].0);
+    let result_str = format!(
+        "{{{}\n{}Field::{}}}",
+        all_check_functions,
+        struct_name,
+        derive::field_variant_name(parsed_path.field_path.trim_start_matches("r#"))
+    );
+    result_str.parse().unwrap()
+}
 
-    if let Some(field_path) = current_field_path.take() {
-        current_struct_fields.push(field_path);
+/// Returns the path with the zero-based declaration index of the field from `#[derive(StructPath)]`,
+/// so `path_with_index!(TestStructChild::child_value_num; case = "camel")` returns `("childValueNum", 1)`
+/// for the positional formats such as the fixed-column CSV.
+#[proc_macro]
+pub fn path_with_index(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    if parsed_path.found_structs.len() != 1 || split_field_path(&parsed_path.field_path).len() > 1 {
+        panic!(
+            "Expected a direct struct field for the field index: {}",
+            parsed_path.field_path
+        );
     }
+    let struct_name = struct_type_name(&parsed_path.found_structs[0].0);
+    let result_str = format!(
+        "{{{}\n({:?}, {}Field::{}.index())}}",
+        all_check_functions,
+        final_field_path(&parsed_path),
+        struct_name,
+        derive::field_variant_name(parsed_path.field_path.trim_start_matches("r#"))
+    );
+    result_str.parse().unwrap()
+}
 
-    if let Some(struct_name) = current_struct_name.take() {
-        if let Some(field_path) = current_field_path.take() {
-            current_struct_fields.push(field_path);
-        }
-        if !current_struct_fields.is_empty() {
-            found_structs.push((struct_name, current_struct_fields.drain(..).collect()));
-        } else {
-            panic!("Unexpected comma with empty fields for {}!", struct_name);
+fn final_field_paths(parsed_paths: &ParsedPaths) -> Vec<String> {
+    let mut all_final_fields: Vec<String> = Vec::with_capacity(16);
+
+    for (struct_name, struct_fields) in &parsed_paths.found_structs {
+        for field_path in struct_fields {
+            all_final_fields.push(final_struct_field_path(
+                &parsed_paths.options,
+                struct_name,
+                field_path,
+            ))
         }
-    } else {
-        panic!("Unexpected comma with empty definitions!");
     }
 
-    let all_check_functions = generate_checks_code_for(&found_structs);
+    if !all_final_fields.is_empty() {
+        all_final_fields
+    } else {
+        panic!("Empty struct fields")
+    }
+}
 
-    let mut all_final_fields: Vec<String> = Vec::with_capacity(16);
+/// Merges all the paths into the GraphQL selection set, converting the segments with the options.
+fn final_selection_set(parsed_paths: &ParsedPaths) -> SelectionSet {
+    let mut all_segments: Vec<Vec<String>> = Vec::with_capacity(16);
 
-    for (_, struct_fields) in &found_structs {
+    for (struct_name, struct_fields) in &parsed_paths.found_structs {
         for field_path in struct_fields {
-            let mut final_field_path = field_path.clone().replace('~', ".");
-            if !options.is_empty() {
-                final_field_path = apply_options(&options, final_field_path);
-            }
-            all_final_fields.push(format!("\"{}\"", final_field_path))
+            let field_path = match included_root_segment(&parsed_paths.options, struct_name) {
+                Some(root_segment) => format!("{}.{}", root_segment, field_path),
+                None => field_path.to_string(),
+            };
+            all_segments.push(converted_segments(&parsed_paths.options, &field_path));
         }
     }
 
-    if !all_final_fields.is_empty() {
-        format!(
-            "{{{}\n[{}]}}",
-            all_check_functions,
-            all_final_fields.join(",")
-        )
-        .parse()
-        .unwrap()
+    if !all_segments.is_empty() {
+        SelectionSet::from_paths(all_segments)
     } else {
         panic!("Empty struct fields")
     }
 }
 
 #[inline]
-fn parse_multiple_fields(group_stream: TokenStream, found_struct_fields: &mut Vec<String>) {
-    let mut current_field_path: Option<String> = None;
+fn final_struct_field_path(
+    options: &HashMap<String, String>,
+    struct_name: &str,
+    field_path: &str,
+) -> String {
+    let field_path = match included_root_segment(options, struct_name) {
+        Some(root_segment) => format!("{}.{}", root_segment, field_path),
+        None => tagged_field_path(options, struct_name, field_path),
+    };
+    let final_path = finalize_path(options, apply_versioned_options(options, field_path));
+    apply_template(options, struct_name, final_path)
+}
 
-    for token_tree in group_stream.into_iter() {
-        match token_tree {
-            TokenTree::Ident(id) => {
-                if let Some(ref mut field_path) = &mut current_field_path {
-                    field_path.push_str(id.to_string().as_str())
-                } else {
-                    current_field_path = Some(id.to_string());
-                }
-            }
-            TokenTree::Punct(punct) if punct == ',' => {
-                if let Some(field_path) = current_field_path.take() {
-                    found_struct_fields.push(field_path);
-                    current_field_path = None;
-                } else {
-                    panic!(
-                        "Unexpected punctuation input for struct path group parameters: {:?}",
-                        punct
-                    )
-                }
-            }
-            TokenTree::Punct(punct) if punct == '.' => {
-                if let Some(ref mut field_path) = &mut current_field_path {
-                    field_path.push('.');
-                } else {
-                    panic!(
-                        "Unexpected punctuation input for struct path group parameters: {:?}",
-                        punct
-                    )
+/// Substitutes the `{struct}` and `{path}` placeholders of the `template` option,
+/// the struct name is converted with the same `case` as the fields.
+#[inline]
+fn apply_template(
+    options: &HashMap<String, String>,
+    struct_name: &str,
+    final_path: String,
+) -> String {
+    match options.get("template") {
+        Some(template) => {
+            let struct_segment = match options.get("case") {
+                Some(case_value) => convert_field_case(&root_segment(struct_name), case_value),
+                None => simple_struct_name(struct_name).to_string(),
+            };
+            template
+                .replace("{struct}", &struct_segment)
+                .replace("{path}", &final_path)
+        }
+        None => final_path,
+    }
+}
+
+/// Applies the options to the whole final path rather than to its segments.
+fn finalize_path(options: &HashMap<String, String>, final_path: String) -> String {
+    let final_path = match length_segment(options) {
+        Some(length_segment) => {
+            format!("{}{}{}", final_path, delim_option(options), length_segment)
+        }
+        None => final_path,
+    };
+    let segment_conversion = SegmentConversion::new(options);
+    let final_path = match options.get("prefix") {
+        Some(prefix) => format!(
+            "{}{}{}",
+            segment_conversion.convert_affix("prefix", prefix),
+            delim_option(options),
+            final_path
+        ),
+        None => final_path,
+    };
+    let final_path = match options.get("suffix") {
+        Some(suffix) => format!(
+            "{}{}{}",
+            final_path,
+            delim_option(options),
+            segment_conversion.convert_affix("suffix", suffix)
+        ),
+        None => final_path,
+    };
+    append_checksum(options, final_path)
+}
+
+/// The segment addressing the string length with the `length` option:
+/// `length = true` gives `length`, while the other values are used as the segment names.
+#[inline]
+fn length_segment(options: &HashMap<String, String>) -> Option<&str> {
+    match options.get("length").map(|s| s.as_str()) {
+        Some("true") => Some("length"),
+        Some("false") | None => None,
+        Some(length_segment) => Some(length_segment),
+    }
+}
+
+/// Appends the `.xxxx` suffix with the lowest 16 bits of the 32-bit FNV-1a hash
+/// of the final path in lowercase hex when `checksum = true`.
+fn append_checksum(options: &HashMap<String, String>, final_path: String) -> String {
+    match options.get("checksum").map(|s| s.as_str()) {
+        Some("true") => {
+            let hash = final_path.bytes().fold(0x811c9dc5_u32, |hash, byte| {
+                (hash ^ byte as u32).wrapping_mul(0x01000193)
+            });
+            format!("{}.{:04x}", final_path, hash & 0xffff)
+        }
+        Some("false") | None => final_path,
+        Some(others) => panic!("Unknown checksum option value is specified: {}", others),
+    }
+}
+
+#[inline]
+fn json_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Finds the commas separating the top level items, skipping the commas inside
+/// the generic arguments such as `HashMap<String, u64>`.
+fn top_level_comma_positions(tokens: &[TokenTree]) -> Vec<usize> {
+    let mut angle_brackets_counter = 0;
+    let mut prev_joint_punct: Option<char> = None;
+    let mut comma_positions = Vec::new();
+    for (pos, token_tree) in tokens.iter().enumerate() {
+        if let TokenTree::Punct(punct) = token_tree {
+            match punct.as_char() {
+                '<' => angle_brackets_counter += 1,
+                '>' if !matches!(prev_joint_punct, Some('-') | Some('=')) => {
+                    angle_brackets_counter -= 1
                 }
+                ',' if angle_brackets_counter == 0 => comma_positions.push(pos),
+                _ => {}
             }
-            others => {
-                panic!(
-                    "Unexpected input for struct path group parameters: {:?}",
-                    others
-                )
-            }
+            prev_joint_punct = Some(punct.as_char()).filter(|_| punct.spacing() == Spacing::Joint);
+        } else {
+            prev_joint_punct = None;
         }
     }
+    comma_positions
+}
+
+/// Returns the separate output segment of the bracket: the variant name of the enum map keys
+/// or the MongoDB positional operator `$` of the arrays.
+#[inline]
+fn bracket_segment(bracket_content: &str) -> Option<&str> {
+    match bracket_content {
+        POSITIONAL_OPERATOR => Some(POSITIONAL_OPERATOR),
+        _ => enum_key_variant(bracket_content),
+    }
+}
+
+const POSITIONAL_OPERATOR: &str = "$";
 
-    if let Some(field_path) = current_field_path.take() {
-        found_struct_fields.push(field_path);
+/// The wildcard of all the array elements for the schema paths such as `items[*].name`.
+const WILDCARD_INDEX: &str = "*";
+
+/// Returns the variant name of the enum map key bracket such as `TestKind::Primary`.
+#[inline]
+fn enum_key_variant(bracket_content: &str) -> Option<&str> {
+    let key_parts: Vec<&str> = bracket_content.split("::").collect();
+    let is_ident = |part: &str| {
+        part.chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && part.chars().all(|c| c.is_alphanumeric() || c == '_')
+    };
+    if key_parts.len() > 1 && key_parts.iter().all(|part| is_ident(part)) {
+        key_parts.last().copied()
+    } else {
+        None
     }
 }
 
+/// Returns the path for the specified struct fields.
+///
+/// Besides the fields, the paths support:
+/// - Slice ranges with literal bounds such as `items[1..3]`, `items[2..]` or `items[..]`;
+/// - Generic structs with the turbofish arguments such as `path!(TestContainer::<u64>::value)`, verified
+///   for the specified arguments and ignored in the output;
+/// - Enum variants as the roots such as `path!(TestTupleVariant::Pair::1)` returning the externally-tagged `Pair.1`,
+///   verified by matching the variant as `enum_path!` does;
+/// - Tuple struct and tuple fields referenced by their positions such as `path!(TestPoint::0)` or `path!(TestStructWrapper::pair.1.0)`;
+/// - Literal array indices such as `items[0].child_value_str` or `matrix[0][1]`, verified by indexing the fields;
+/// - Wildcard array segments for the schema paths such as `items[*].child_value_str`, verified as the indexed fields
+///   and emitted as is. The wildcard can't be combined with the numeric indices of the same segment such as `matrix[*][0]`;
+/// - String literal segments inserted verbatim (without the case conversion), such as the map keys in
+///   `path!(TestStructSettings::by_name."config".child_value_str)` returning `by_name.config.child_value_str`.
+///   The literal segments aren't struct fields, so only the segments before the first literal one are verified;
+/// - Per-segment `@raw` annotation emitting the segment as is without the case conversion and abbreviations,
+///   such as `path!(TestStructHttp::httpURL@raw.status_code; case = "camel")` returning `httpURL.statusCode`;
+/// - Map values traversal with a placeholder key segment such as `path!(TestStructSettings::by_name{*}child_value_str)`
+///   returning `by_name.*.child_value_str`, verified through any of the map values. The key segment is emitted
+///   as `*` without the case conversion or as a string literal such as `by_name{"$key"}child_value_str`;
+/// - Enum keys of maps such as `by_kind[TestKind::Primary].value`, producing the variant name as a segment: `by_kind.Primary.value`;
+/// - MongoDB positional operator of arrays such as `path!(TestStructOrder::items[$].child_value_str)`
+///   returning `items.$.child_value_str`. The `$` segment is emitted as is (joined with `delim` as the others)
+///   without the case conversion, while the array field and its element fields are verified;
+/// - Explicit traversal steps between the segments: `~` for `Option`, `?` for the `Ok` values of `Result`, `#` for the elements of `Vec`, arrays and slices
+///   (verified by slicing, so the `Option` fields are rejected) and `*` for `Deref` such as `Arc` or `Box`,
+///   composed into the multi-step separators such as `~*` for `Option<Arc<_>>`, `?~` for `Result<Option<_>, _>` or `~~` flattening `Option<Option<_>>`
+///   (a single output separator for the both layers), so `path!(TestStructShared::shared*child_value_str)`
+///   verifies `(*shared).child_value_str` and returns `shared.child_value_str`.
+///   The macros can't see the field types to insert the dereferencing themselves, so the plain `.` and `~` steps
+///   rely on the auto-deref of `Box`, `Rc` and `Arc`, while `*` makes it explicit for the other `Deref` wrappers;
+/// - Fields of the enum variants inside the structs as `field::Enum::Variant::variant_field`, such as
+///   `path!(TestMachine::state::TestMachineState::Running::progress)` returning `state.progress` for the tagged unions.
+///   The enum field name must start with a lowercase letter or `_` to tell it apart from the module paths of the structs.
+///   The enum path (qualified as in the patterns) is verified by matching the variant, and the tuple variant fields
+///   are referenced by their positions such as `state::TestMachineState::Paused::0`;
+///
+/// Options (the defaults can be baked into the wrapper macros with `define_path_macros!`):
+/// - `delim` - the delimiter between segments (`.` by default);
+/// - `flatten_delim` - the delimiter of the deeper segments, while `delim` only joins the first two of them,
+///   so `delim = "/", flatten_delim = "."` produces `value_child/child_value_str.nested_value` for the hybrid formats;
+/// - `case` - the case conversion for segments: `camel`, `pascal`, `kebab`, `screaming` (or `upper_snake`) or `snake`.
+///   The cases can be written as `case = Case::Camel` as well: the value is matched by its name as text,
+///   so it needs no `use` (the proc-macro crate can't re-export `convert_case::Case` anyway) and only
+///   `Case::Camel`, `Case::Pascal`, `Case::Kebab`, `Case::UpperSnake` and `Case::Snake` are accepted.
+///   All of them except `snake` treat the fields as the snake case ones, while `snake` detects
+///   the source case to normalize the mixed case fields such as `someField` to `some_field`;
+/// - `transform` - the plain transform of segments without the case conversion word splitting:
+///   `lower-raw` lowercases the segments keeping the underscores, so `My_Field` is `my_field`;
+/// - `abbrev` - the comma separated list of `source=abbreviation` pairs replacing whole segments
+///   before the case conversion, such as `abbrev = "identifier=id,configuration=config"`;
+/// - `digit_style` - how the case conversion treats the numeric words such as `line_2`:
+///   `glued` (default) produces `line2`, `separated` keeps the underscore and produces `line_2`;
+/// - `format` - the output format preset:
+///   - `url` percent-encodes every segment and joins them with `/`;
+///   - `jsonpointer` produces RFC 6901 JSON Pointers such as `/value_child/child_value_str`
+///     with `~` and `/` in segments escaped as `~0` and `~1`;
+///   - `json-schema` produces JSON Schema `$ref` pointers such as `#/properties/value_child/properties/child_value_str`;
+///   - `graphql` produces GraphQL selection sets such as `value_child { child_value_str }`,
+///     `paths!` merges all the paths into a single selection set grouping the shared prefixes;
+/// - `root_first` - when `true`, `path!` starts with the first struct name in snake case as a root segment,
+///   so `path!(TestStructParent::value_child, TestStructChild::child_value_str; root_first = true)`
+///   returns `test_struct_parent.value_child.child_value_str`;
+/// - `on_empty_segment` - how to treat the empty segments such as the fields abbreviated to the empty strings:
+///   `error` (default) rejects the path, `skip` drops the empty segments and `keep` preserves them;
+/// - `preserve_leading_underscore` - when `true`, restores the leading underscores removed by the case conversion,
+///   so `_internal` is `_internal` with `case = "camel"` and `_Internal` with `case = "pascal"`;
+/// - `strip_trailing_underscore` - when `true`, strips a single trailing underscore of the keyword fields
+///   such as `type_` or `ref_`, so the segments match their serialized names `type` and `ref`;
+/// - `include_root` - when `true`, `path!` and `paths!` start with the struct name as a root segment
+///   converted with the same `case` as the fields, so `path!(TestStructParent::value_str; include_root = true, case = "camel")`
+///   returns `testStructParent.valueStr`. The chains of structs in `path!` use the first struct name;
+/// - `segment_pattern` - the pattern every segment must match after the case conversion,
+///   such as `segment_pattern = "[a-z][a-zA-Z0-9]*"`. Supports literal characters, `.`, character classes
+///   with ranges and `^` negation, and the `*`, `+`, `?` quantifiers;
+/// - `strict` - when `true`, additionally verifies that every field followed by `~` is an `Option`;
+/// - `prefix` - the fixed root segment emitted verbatim before the path and the delimiter,
+///   so `path!(TestStructParent::value_str; prefix = "metadata")` returns `metadata.value_str`;
+/// - `suffix` - the fixed trailing segment emitted verbatim after the path and the delimiter,
+///   so `path!(TestStructParent::value_str; suffix = "$")` returns `value_str.$`;
+/// - `pipeline` - the comma separated order of the conversion stages `abbrev`, `case`, `transform`, `prefix`
+///   and `suffix`, such as `pipeline = "case,abbrev"` to abbreviate the converted segments. The stages missing
+///   in the pipeline follow in the default order above, while the stages without the configured options are skipped.
+///   The `prefix` and `suffix` segments are converted by the stages following them, so `pipeline = "prefix,case"`
+///   converts the prefix case as well;
+/// - `template` - the template of the final path with the `{struct}` and `{path}` placeholders, such as
+///   `path!(User::address.city; template = "{struct}[{path}]")` returning `User[address.city]` for the log formats.
+///   The struct name is the first struct simple name converted with the same `case` as the fields;
+/// - `length` - when `true`, addresses the length of a string field as `value_str.length` for the validation schemas,
+///   additionally verifying the field is a string. The other values are used as the segment names instead of `length`;
+/// - `check` - when `false`, skips the fields verification, such as for the generic type parameters
+///   in `path!(T::value_str; check = false)`;
+/// - `tagged` - when `false`, omits the variant name of the enum roots such as `path!(TestTupleVariant::Pair::1; tagged = false)`
+///   returning `1` instead of the externally-tagged `Pair.1`;
+/// - `boundary` - the marker between the structs of the comma form instead of the delimiter,
+///   so `path!(TestStructParent::value_child, TestStructChild::child_value_str; boundary = "->")`
+///   returns `value_child->child_value_str`, while the delimiter stays between the fields of the same struct;
+/// - `acronym_segments` - when `upper`, uppercases the whole segments of the common acronyms after the case conversion
+///   (`api`, `css`, `html`, `http`, `id`, `ip`, `json`, `sql`, `uri`, `url`, `uuid` and `xml`), so
+///   `path!(TestStructIdentified::owner.url; case = "camel", acronym_segments = "upper")` returns `owner.URL`;
+/// - `version` - the version discriminator of the versioned schemas prepended as its own segment
+///   without the case conversion, so `path!(TestStructParent::value_child.child_value_str; version = "v2")`
+///   returns `v2.value_child.child_value_str`;
+/// - `reserved` - the comma-separated keys reserved by the format such as `reserved = "_id,__typename"`,
+///   rejecting the paths with any (converted) segment equal to one of them;
+/// - `checksum` - when `true`, appends a short stable hash of the path for cache-busting, such as
+///   `value_str.b5b6`. The hash is the lowest 16 bits of the 32-bit FNV-1a hash of the final path
+///   as 4 lowercase hex digits;
+///
+/// The fields after `~` are verified through the `Option` (or another `Iter`-based field) as well:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub opt_value_child: Option<TestStructChild>,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub child_value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::opt_value_child~child_value_strr);
+/// ```
+///
+/// Every `~` step unwraps a single layer, so `~~` verifies the fields inside `Option<Option<_>>`:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub opt_opt_value_child: Option<Option<TestStructChild>>,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub child_value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::opt_opt_value_child~~child_value_strr);
+/// ```
+///
+/// The element fields after `#` are verified as well:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub vec_value_child: Vec<TestStructChild>,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub child_value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::vec_value_child#child_value_strr);
+/// ```
+///
+/// The `Ok` value fields after `?` are verified as well:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub result_value_child: Result<TestStructChild, String>,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub child_value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::result_value_child?child_value_strr);
+/// ```
+///
+/// The map value fields after `{*}` are verified as well:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+/// use std::collections::HashMap;
+///
+/// pub struct TestStructParent {
+///     pub by_name: HashMap<String, TestStructChild>,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub child_value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::by_name{*}child_value_strr);
+/// ```
+///
+/// With `strict = true` the fields followed by `~` must be `Option`s, so any other `Iter`-based field is rejected:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub vec_value_child: Vec<TestStructChild>,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub child_value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::vec_value_child~child_value_str; strict = true);
+/// ```
+///
+/// The segments violating `segment_pattern` are rejected as well:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::value_str; segment_pattern = "[a-z]+");
+/// ```
+///
+/// Including the literal and the map key segments:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::"Bad Seg".value_str; segment_pattern = "[a-z_]+");
+/// ```
+///
+/// With `length = true` the field must be a string:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_num: u64,
+/// }
+///
+/// let s: &str = path!(TestStructParent::value_num; length = true);
+/// ```
+///
+/// The `*` steps require the `Deref` fields:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_child: TestStructChild,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub child_value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::value_child*child_value_str);
+/// ```
+///
+/// The indexed fields must be indexable:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_child: TestStructChild,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub child_value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::value_child[0].child_value_str);
+/// ```
+///
+/// The wildcard indices can't be combined with the numeric ones:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub matrix: Vec<Vec<TestStructChild>>,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub child_value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::matrix[*][0].child_value_str);
+/// ```
+///
+/// The enum variant fields are verified by matching the variant:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+///
+/// pub struct TestMachine {
+///     pub state: TestMachineState,
+/// }
+///
+/// pub enum TestMachineState {
+///     Idle,
+///     Running { progress: u8 },
+/// }
+///
+/// let s: &str = path!(TestMachine::state::TestMachineState::Idle::progress);
+/// ```
+///
+/// The segments colliding with the `reserved` keys are rejected:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructDocument {
+///     pub _id: String,
+/// }
+///
+/// let s: &str = path!(TestStructDocument::_id; reserved = "_id,__typename");
+/// ```
+///
+/// The unknown cases are rejected:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::value_str; case = "screaming_kebab");
+/// ```
+///
+/// The empty segments (such as the fields abbreviated to the empty strings) are rejected by default:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// let s: &str = path!(TestStructParent::value_str; abbrev = "value_str=");
+/// ```
 #[proc_macro]
 pub fn path(struct_path_stream: TokenStream) -> TokenStream {
-    let mut current_struct_name: Option<String> = None;
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions = generate_checks_for(
+        &parsed_path.found_structs,
+        &parsed_path.field_spans,
+        &parsed_path.options,
+    );
+    let final_field_path = final_field_path(&parsed_path);
+    let result_stream = quote! {{ #all_check_functions #final_field_path }};
+    result_stream.into()
+}
 
-    let mut opened_struct = false;
-    let mut colons_counter = 0;
-    let mut options_opened = false;
+/// Defines a `const` item of the path with the checks in a separate `const _` item, so
+/// `const_path!(pub VALUE_STR_PATH = TestStructParent::value_str; case = "camel")` expands to
+/// `pub const VALUE_STR_PATH: &str = "valueStr";` initialized with the bare literal.
+#[proc_macro]
+pub fn const_path(const_path_stream: TokenStream) -> TokenStream {
+    let mut const_tokens: Vec<TokenTree> = const_path_stream.into_iter().collect();
+    let eq_pos = const_tokens
+        .iter()
+        .position(
+            |token_tree| matches!(token_tree, TokenTree::Punct(punct) if punct.as_char() == '='),
+        )
+        .unwrap_or_else(|| panic!("Expected a const definition as `NAME = Struct::field`!"));
+    let path_tokens = const_tokens.split_off(eq_pos + 1);
+    const_tokens.pop();
+    let const_name = match const_tokens.pop() {
+        Some(TokenTree::Ident(id)) => id,
+        others => panic!("Expected a const name before `=`: {:?}", others),
+    };
+    let const_vis: proc_macro2::TokenStream =
+        const_tokens.into_iter().collect::<TokenStream>().into();
+    let const_name = proc_macro2::Ident::new(&const_name.to_string(), const_name.span().into());
 
-    let mut current_field_path: Option<String> = None;
-    let mut current_full_field_path: Option<String> = None;
+    let parsed_path = match parse_checked_path(path_tokens.into_iter().collect()) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions = generate_checks_for(
+        &parsed_path.found_structs,
+        &parsed_path.field_spans,
+        &parsed_path.options,
+    );
+    let final_field_path = final_field_path(&parsed_path);
+    let result_stream = quote! {
+        const _: () = { #all_check_functions };
+        #const_vis const #const_name: &str = #final_field_path;
+    };
+    result_stream.into()
+}
 
-    let mut current_option_name: Option<String> = None;
-    let mut expect_option_value: bool = false;
+/// Returns the path as `path!` does, or an empty string without the checks for the empty input
+/// such as `path_opt!()` or `path_opt!(; case = "camel")` in the generated macros.
+#[proc_macro]
+pub fn path_opt(struct_path_stream: TokenStream) -> TokenStream {
+    let is_empty_path = match struct_path_stream.clone().into_iter().next() {
+        None => true,
+        Some(TokenTree::Punct(punct)) => punct.as_char() == ';',
+        Some(_) => false,
+    };
+    if is_empty_path {
+        quote!("").into()
+    } else {
+        path(struct_path_stream)
+    }
+}
 
-    let mut options: HashMap<String, String> = HashMap::new();
-    let mut found_structs: Vec<(String, Vec<String>)> = Vec::new();
+/// Asserts at compile time that two path macros produce the same path, so the renames
+/// such as `assert_path_eq_macro!(path!(Old::a.b), path!(New::x.y))` preserve the serialized paths:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+///     pub value_num: u64,
+/// }
+///
+/// assert_path_eq_macro!(path!(TestStructParent::value_str), path!(TestStructParent::value_num));
+/// ```
+#[proc_macro]
+pub fn assert_path_eq_macro(assert_stream: TokenStream) -> TokenStream {
+    let assert_tokens: Vec<TokenTree> = assert_stream.into_iter().collect();
+    let (left_tokens, right_tokens) = match top_level_comma_positions(&assert_tokens).as_slice() {
+        [comma_pos] => (
+            &assert_tokens[..*comma_pos],
+            &assert_tokens[*comma_pos + 1..],
+        ),
+        [comma_pos, trailing_pos] if *trailing_pos == assert_tokens.len() - 1 => (
+            &assert_tokens[..*comma_pos],
+            &assert_tokens[*comma_pos + 1..*trailing_pos],
+        ),
+        _ => panic!("Expected two path macros invocations separated by a comma!"),
+    };
+    if left_tokens.is_empty() || right_tokens.is_empty() {
+        panic!("Expected two path macros invocations separated by a comma!");
+    }
+    let left: TokenStream = left_tokens.iter().cloned().collect();
+    let right: TokenStream = right_tokens.iter().cloned().collect();
 
-    for token_tree in struct_path_stream.into_iter() {
-        match token_tree {
-            TokenTree::Ident(id) if current_struct_name.is_none() => {
-                current_struct_name = Some(id.to_string());
-            }
-            TokenTree::Punct(punct)
-                if current_struct_name.is_some()
-                    && !opened_struct
-                    && punct == ':'
-                    && colons_counter < 2 =>
-            {
-                colons_counter += 1;
-                if colons_counter > 1 {
-                    opened_struct = true;
-                }
-            }
-            TokenTree::Ident(id) if opened_struct => {
-                colons_counter = 0;
-                if let Some(ref mut field_path) = &mut current_field_path {
-                    field_path.push_str(id.to_string().as_str())
-                } else {
-                    current_field_path = Some(id.to_string());
-                }
-            }
-            TokenTree::Punct(punct)
-                if current_struct_name.is_some()
-                    && opened_struct
-                    && punct == ':'
-                    && colons_counter < 2 =>
-            {
-                colons_counter += 1;
-                opened_struct = false;
-                if let Some(ref mut field_path) = current_field_path.take() {
-                    if let Some(ref mut struct_name) = &mut current_struct_name {
-                        struct_name.push_str("::");
-                        struct_name.push_str(field_path);
-                    }
-                }
-            }
-            TokenTree::Punct(punct) if opened_struct && (punct == '.' || punct == '~') => {
-                if let Some(ref mut field_path) = &mut current_field_path {
-                    field_path.push(punct.as_char());
-                } else {
-                    panic!(
-                        "Unexpected punctuation input for struct path group parameters: {:?}",
-                        punct
-                    )
-                }
-            }
-            TokenTree::Punct(punct) if !options_opened && opened_struct && punct == ',' => {
-                opened_struct = false;
-                colons_counter = 0;
-                if let Some(struct_name) = current_struct_name.take() {
-                    if let Some(field_path) = current_field_path.take() {
-                        found_structs.push((struct_name, vec![field_path.clone()]));
-
-                        if let Some(full_field_path) = &mut current_full_field_path {
-                            full_field_path.push('.');
-                            full_field_path.push_str(field_path.as_str());
-                        } else {
-                            current_full_field_path = Some(field_path)
-                        }
-                    } else {
-                        panic!("Unexpected comma with empty fields for {}!", struct_name);
-                    }
-                } else {
-                    panic!("Unexpected comma with empty definitions!");
-                }
-            }
-            TokenTree::Punct(punct) if punct == ';' && opened_struct && !options_opened => {
-                options_opened = true;
-                opened_struct = false;
-            }
-            TokenTree::Ident(id) if options_opened && !expect_option_value => {
-                current_option_name = Some(id.to_string())
-            }
-            TokenTree::Ident(id) if options_opened && expect_option_value => {
-                expect_option_value = false;
-                match current_option_name.take() {
-                    Some(option_name) => {
-                        options.insert(option_name, id.to_string());
-                    }
-                    _ => {
-                        panic!("Wrong options format")
-                    }
-                }
-            }
-            TokenTree::Literal(lit) if options_opened && expect_option_value => {
-                expect_option_value = false;
-                match current_option_name.take() {
-                    Some(option_name) => {
-                        let lit_str = lit.to_string();
-                        options.insert(
-                            option_name,
-                            lit_str.as_str()[1..lit_str.len() - 1].to_string(),
-                        );
-                    }
-                    _ => {
-                        panic!("Wrong options format")
-                    }
-                }
-            }
-            TokenTree::Punct(punct) if options_opened && punct == '=' => {
-                expect_option_value = true;
-            }
-            TokenTree::Punct(punct) if options_opened && punct == ',' => {
-                expect_option_value = false;
-            }
-            others => {
-                panic!("Unexpected input for struct path parameters: {:?}", others)
-            }
+    let result_str = format!(
+        r#"const _: () = {{
+            {}
+            if !_path_eq({}, {}) {{
+                panic!("{{}}", {:?});
+            }}
+        }};"#,
+        CONST_PATH_EQ_FN,
+        left,
+        right,
+        format!("The paths are not equal: {} and {}", left, right)
+    );
+    result_str.parse().unwrap()
+}
+
+/// Asserts at compile time that the paths are unique (reporting the first duplicate), so the renames don't produce
+/// the colliding serialized names in `assert_unique_paths!([path!(A::x), path!(B::y)])`:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub value_str: String,
+/// }
+///
+/// assert_unique_paths!([path!(TestStructParent::value_str), path!(TestStructChild::value_str)]);
+/// ```
+#[proc_macro]
+pub fn assert_unique_paths(assert_stream: TokenStream) -> TokenStream {
+    let mut assert_tokens: Vec<TokenTree> = assert_stream.into_iter().collect();
+    if let [TokenTree::Group(group)] = assert_tokens.as_slice() {
+        if group.delimiter() == Delimiter::Bracket {
+            assert_tokens = group.stream().into_iter().collect();
         }
     }
+    let all_paths: Vec<TokenStream> = derive::split_top_level(&assert_tokens)
+        .into_iter()
+        .map(|path_tokens| path_tokens.iter().cloned().collect())
+        .collect();
 
-    if let Some(struct_name) = current_struct_name.take() {
-        if let Some(field_path) = current_field_path.take() {
-            found_structs.push((struct_name, vec![field_path.clone()]));
+    let result_str = format!(
+        r#"const _: () = {{
+            {}
+            const PATHS: &[&str] = &[{}];
+            let mut left_pos = 0;
+            while left_pos < PATHS.len() {{
+                let mut right_pos = left_pos + 1;
+                while right_pos < PATHS.len() {{
+                    if _path_eq(PATHS[left_pos], PATHS[right_pos]) {{
+                        panic!("{{}}", PATHS[left_pos]);
+                    }}
+                    right_pos += 1;
+                }}
+                left_pos += 1;
+            }}
+        }};"#,
+        CONST_PATH_EQ_FN,
+        all_paths
+            .iter()
+            .map(|path| path.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    result_str.parse().unwrap()
+}
 
-            if let Some(full_field_path) = &mut current_full_field_path {
-                full_field_path.push('.');
-                full_field_path.push_str(field_path.as_str());
-            } else {
-                current_full_field_path = Some(field_path)
+const CONST_PATH_EQ_FN: &str = r#"
+    const fn _path_eq(left: &str, right: &str) -> bool {
+        let (left, right) = (left.as_bytes(), right.as_bytes());
+        if left.len() != right.len() {
+            return false;
+        }
+        let mut pos = 0;
+        while pos < left.len() {
+            if left[pos] != right[pos] {
+                return false;
             }
+            pos += 1;
         }
+        true
     }
+"#;
+
+/// Returns the path segment at the specified index, so
+/// `at_depth!(TestStructParent::value_child.child_value_str, 1)` returns `"child_value_str"`.
+/// The index goes after the path and before the options and must be in range.
+/// The index counts the output segments such as `$` of `items[$]`, without the `version` segment:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// let s: &str = at_depth!(TestStructParent::value_str, 1);
+/// ```
+#[proc_macro]
+pub fn at_depth(struct_path_stream: TokenStream) -> TokenStream {
+    let (path_stream, depth) = split_depth_argument(struct_path_stream);
+    let parsed_path = match parse_checked_path(path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    let segments = converted_segments(&parsed_path.options, &parsed_path.field_path);
+
+    let result_str = match segments.get(depth) {
+        Some(segment) => format!("{{{}\n{:?}}}", all_check_functions, segment),
+        None => format!(
+            "compile_error!(\"Depth {} is out of range for a path with {} segments\")",
+            depth,
+            segments.len()
+        ),
+    };
+    result_str.parse().unwrap()
+}
+
+/// Returns the path checking it has exactly the specified number of segments, so
+/// `path_exact_depth!(TestStructParent::value_child.child_value_str, 2)` returns `"value_child.child_value_str"`.
+/// The segments are counted in the output, so `items[$]` has the `items` and `$` segments.
+/// Both the too shallow and the too deep paths are rejected:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// let s: &str = path_exact_depth!(TestStructParent::value_str, 2);
+/// ```
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_child: TestStructChild,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub child_value_str: String,
+/// }
+///
+/// let s: &str = path_exact_depth!(TestStructParent::value_child.child_value_str, 1);
+/// ```
+#[proc_macro]
+pub fn path_exact_depth(struct_path_stream: TokenStream) -> TokenStream {
+    let (path_stream, depth) = split_depth_argument(struct_path_stream);
+    let parsed_path = match parse_checked_path(path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    let segments_count = converted_segments(&parsed_path.options, &parsed_path.field_path).len();
 
-    if let Some(full_field_path) = current_full_field_path.take() {
-        let all_check_functions = generate_checks_code_for(&found_structs);
-        let final_field_path = apply_options(&options, full_field_path).replace('~', ".");
-        let result_str = format!("{{{}\n\"{}\"}}", all_check_functions, final_field_path);
-        result_str.parse().unwrap()
+    let result_str = if segments_count == depth {
+        format!(
+            "{{{}\n{:?}}}",
+            all_check_functions,
+            final_field_path(&parsed_path)
+        )
     } else {
-        panic!("Unexpected empty path definition!");
+        format!(
+            "compile_error!(\"Expected a path with exactly {} segments, but {} has {} segments\")",
+            depth, parsed_path.field_path, segments_count
+        )
+    };
+    result_str.parse().unwrap()
+}
+
+/// Returns the number of the path segments as a `usize` constant, counted as `path_exact_depth!` does,
+/// so `segment_count!(TestStructParent::value_child.child_value_str)` returns `2`.
+#[proc_macro]
+pub fn segment_count(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    let segments_count = converted_segments(&parsed_path.options, &parsed_path.field_path).len();
+
+    format!("{{{}\n{}usize}}", all_check_functions, segments_count)
+        .parse()
+        .unwrap()
+}
+
+/// Splits the numeric argument following the path before the options, such as `1` in
+/// `at_depth!(TestStructParent::value_child.child_value_str, 1; case = "camel")`.
+fn split_depth_argument(struct_path_stream: TokenStream) -> (TokenStream, usize) {
+    let mut path_tokens: Vec<TokenTree> = struct_path_stream.into_iter().collect();
+    let options_pos = path_tokens
+        .iter()
+        .position(
+            |token_tree| matches!(token_tree, TokenTree::Punct(punct) if punct.as_char() == ';'),
+        )
+        .unwrap_or(path_tokens.len());
+
+    let depth: usize = match &path_tokens[..options_pos] {
+        [.., TokenTree::Punct(punct), TokenTree::Literal(lit)] if punct.as_char() == ',' => {
+            match lit.to_string().parse() {
+                Ok(depth) => depth,
+                Err(_) => panic!("Unexpected depth index for struct path: {}", lit),
+            }
+        }
+        _ => panic!("Expected a depth index after struct path!"),
+    };
+    path_tokens.drain(options_pos - 2..options_pos);
+    (path_tokens.into_iter().collect(), depth)
+}
+
+/// Returns the path to a field of an externally-tagged enum variant (the default serde representation),
+/// so `enum_path!(TestMessage::Login::user)` returns `"Login.user"`.
+/// The variant name is emitted as is, while the options are applied to the fields.
+/// The tuple variants fields are referenced by their position, such as `enum_path!(TestEvent::Created::0.id)`
+/// returning `"Created.0.id"`.
+/// The variant fields are checked as well:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+///
+/// pub enum TestMessage {
+///     Login { user: String },
+/// }
+///
+/// let s: &str = enum_path!(TestMessage::Login::password);
+/// ```
+#[proc_macro]
+pub fn enum_path(enum_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(enum_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    if parsed_path.found_structs.len() != 1 {
+        panic!("Expected a single enum variant path definition!");
     }
+    let (variant_path, field_paths) = &parsed_path.found_structs[0];
+    let (enum_name, variant_name) = match variant_path.rsplit_once("::") {
+        Some(enum_and_variant) => enum_and_variant,
+        None => panic!(
+            "Expected an enum variant path such as Enum::Variant::field instead of {}!",
+            variant_path
+        ),
+    };
+
+    let all_check_functions = generate_enum_checks_code_for(
+        enum_name,
+        variant_name,
+        &field_paths[0],
+        &parsed_path.options,
+    );
+    let final_field_path = format!(
+        "{}{}{}",
+        variant_name,
+        delim_option(&parsed_path.options),
+        apply_options(&parsed_path.options, parsed_path.field_path)
+    );
+    let result_str = format!("{{{}\n{:?}}}", all_check_functions, final_field_path);
+    result_str.parse().unwrap()
 }
 
-#[inline]
-fn generate_checks_code_for(found_structs: &Vec<(String, Vec<String>)>) -> String {
-    let mut all_check_functions = String::new();
+/// Returns the path for a struct reached through an accessor closure, so
+/// `path_via!(|p: &TestStructParent| &p.value_child, TestStructChild::child_value_str)`
+/// returns `"child_value_str"` and also verifies that the closure returns a reference to `TestStructChild`:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+///     pub value_child: TestStructChild,
+/// }
+///
+/// pub struct TestStructChild {
+///     pub child_value_str: String,
+/// }
+///
+/// let s: &str = path_via!(|p: &TestStructParent| &p.value_str, TestStructChild::child_value_str);
+/// ```
+#[proc_macro]
+pub fn path_via(struct_path_stream: TokenStream) -> TokenStream {
+    let mut via_tokens: Vec<TokenTree> = struct_path_stream.into_iter().collect();
+    let via_pos = top_level_comma_positions(&via_tokens).first().copied();
+    let path_tokens = match via_pos {
+        Some(pos) if pos > 0 => via_tokens.split_off(pos + 1),
+        _ => panic!("Expected an accessor closure and a comma before struct path!"),
+    };
+    via_tokens.pop();
 
-    for (struct_name, struct_fields) in found_structs {
-        let check_functions = struct_fields
-            .iter()
-            .map(|field_path| {
-                let field_path_result = field_path.replace('~', ".iter().next().unwrap().");
-                format!(
-                    r#"
+    let parsed_path = match parse_checked_path(path_tokens.into_iter().collect()) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    let via_check_function = format!(
+        r#"
                 {{
                     #[allow(dead_code, unused_variables)]
                     #[cold]
-                    fn _check_sp(test_struct: &{}) {{
-                        let _t = &test_struct.{};
+                    fn _check_sp_via() {{
+                        fn _via<T, R, F: Fn(&T) -> &R>(via: F) {{}}
+                        _via::<_, {}, _>({});
                     }}
                 }}
             "#,
-                    struct_name, field_path_result
-                )
-            })
-            .collect::<Vec<String>>()
-            .join("\n");
-
-        all_check_functions.push_str(&check_functions);
-    }
-    all_check_functions
+        parsed_path.found_structs[0].0,
+        via_tokens.into_iter().collect::<TokenStream>()
+    );
+    let final_field_path = final_field_path(&parsed_path);
+    let result_str = format!(
+        "{{{}{}\n{:?}}}",
+        all_check_functions, via_check_function, final_field_path
+    );
+    result_str.parse().unwrap()
 }
 
+/// Returns the path together with the root struct `TypeId` to be used as a runtime key, so
+/// `path_keyed!(TestStructParent::value_child.child_value_str)` returns
+/// `(std::any::TypeId::of::<TestStructParent>(), "value_child.child_value_str")`.
+#[proc_macro]
+pub fn path_keyed(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    let final_field_path = final_field_path(&parsed_path);
+    let result_str = format!(
+        "{{{}\n(::std::any::TypeId::of::<{}>(), {:?})}}",
+        all_check_functions, parsed_path.found_structs[0].0, final_field_path
+    );
+    result_str.parse().unwrap()
+}
+
+/// Generates the struct of the `&'static str` fields returned by a macro,
+/// for the `define_*!` macros taking no arguments.
+fn define_static_str_struct(
+    define_stream: TokenStream,
+    macro_name: &str,
+    struct_name: &str,
+    field_names: &[&str],
+) -> TokenStream {
+    if !define_stream.is_empty() {
+        return PathError::call_site(format!(
+            "Unexpected input for {}!, expected no arguments",
+            macro_name
+        ))
+        .to_compile_error()
+        .into();
+    }
+    format!(
+        "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\npub struct {} {{ {} }}",
+        struct_name,
+        field_names
+            .iter()
+            .map(|field_name| format!("pub {}: &'static str,", field_name))
+            .collect::<String>()
+    )
+    .parse()
+    .unwrap()
+}
+
+/// Defines `pub struct ColumnRef { pub db: &'static str, pub api: &'static str }` returned by `column_ref!`.
+/// The `column_ref!` results name the struct unqualified, so it has to be in scope where they are used.
+#[proc_macro]
+pub fn define_column_ref(define_stream: TokenStream) -> TokenStream {
+    define_static_str_struct(
+        define_stream,
+        "define_column_ref",
+        "ColumnRef",
+        &["db", "api"],
+    )
+}
+
+/// Returns both the snake case database column and the camel case API name of the path
+/// as the `ColumnRef` defined by `define_column_ref!`, so `column_ref!(User::created_at)` returns
+/// `ColumnRef { db: "created_at", api: "createdAt" }`. The other options apply to both names.
+#[proc_macro]
+pub fn column_ref(struct_path_stream: TokenStream) -> TokenStream {
+    let mut parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    if parsed_path.options.contains_key("case") {
+        panic!(
+            "The case option isn't supported by column_ref!, the names are always snake and camel"
+        );
+    }
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    parsed_path
+        .options
+        .insert("case".to_string(), "snake".to_string());
+    let db_path = final_field_path(&parsed_path);
+    parsed_path
+        .options
+        .insert("case".to_string(), "camel".to_string());
+    let api_path = final_field_path(&parsed_path);
+
+    let result_str = format!(
+        "{{{}\nColumnRef {{ db: {:?}, api: {:?} }}}}",
+        all_check_functions, db_path, api_path
+    );
+    result_str.parse().unwrap()
+}
+
+/// Returns the path segments with their kinds for the structural diff tools, so
+/// `path_annotated!(TestStructOrder::items[0].child_value_str)` returns
+/// `[("items", "field"), ("0", "index"), ("child_value_str", "field")]`.
+/// The kinds are `field` (including the literal segments and the enum keys), `index`
+/// (including the ranges and the positional operator `$`) and `wildcard` for `[*]`.
+#[proc_macro]
+pub fn path_annotated(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    let mut annotated_segments: Vec<String> = Vec::with_capacity(16);
+    for (_, segment) in split_field_path(&parsed_path.field_path) {
+        let (field_name, brackets) = split_segment_brackets(segment);
+        annotated_segments.extend(
+            converted_segments(&parsed_path.options, field_name)
+                .iter()
+                .map(|field_segment| format!("({:?}, \"field\")", field_segment)),
+        );
+        for bracket in brackets {
+            let annotated_segment = match enum_key_variant(bracket) {
+                Some(variant_name) => format!("({:?}, \"field\")", variant_name),
+                None if bracket == WILDCARD_INDEX => format!("({:?}, \"wildcard\")", bracket),
+                None => format!("({:?}, \"index\")", bracket),
+            };
+            annotated_segments.push(annotated_segment);
+        }
+    }
+
+    let result_str = format!(
+        "{{{}\n[{}]}}",
+        all_check_functions,
+        annotated_segments.join(", ")
+    );
+    result_str.parse().unwrap()
+}
+
+/// Returns the path segments joined with a delimiter chosen at runtime as a `String`, so
+/// `path_dyn_delim!(TestStructParent::value_child.child_value_str, delim)` returns
+/// `["value_child", "child_value_str"].join(delim)`.
+/// The delimiter expression goes after the path and before the options, which apply as in `path!`
+/// except `format` and `checksum`:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// let s: String = path_dyn_delim!(TestStructParent::value_str, "/"; checksum = true);
+/// ```
+///
+/// Available with the `std` feature (enabled by default).
+#[cfg(feature = "std")]
+#[proc_macro]
+pub fn path_dyn_delim(struct_path_stream: TokenStream) -> TokenStream {
+    let path_tokens: Vec<TokenTree> = struct_path_stream.into_iter().collect();
+    let options_pos = path_tokens
+        .iter()
+        .position(
+            |token_tree| matches!(token_tree, TokenTree::Punct(punct) if punct.as_char() == ';'),
+        )
+        .unwrap_or(path_tokens.len());
+    let delim_pos = match top_level_comma_positions(&path_tokens[..options_pos]).last() {
+        Some(delim_pos) if *delim_pos + 1 < options_pos => *delim_pos,
+        _ => panic!("Expected a delimiter expression after struct path!"),
+    };
+    let delim_expr: TokenStream = path_tokens[delim_pos + 1..options_pos]
+        .iter()
+        .cloned()
+        .collect();
+
+    let mut parsed_path = match parse_checked_path(
+        path_tokens[..delim_pos]
+            .iter()
+            .chain(path_tokens[options_pos..].iter())
+            .cloned()
+            .collect(),
+    ) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    // The path is built as `path!` does with a placeholder delimiter splitting it back into the segments,
+    // while the options not joining the segments with the delimiter can't be deferred to the runtime
+    if let Some(option_name) = ["format", "checksum"]
+        .into_iter()
+        .find(|option_name| parsed_path.options.contains_key(*option_name))
+    {
+        return PathError::call_site(format!(
+            "The {} option isn't supported by path_dyn_delim!",
+            option_name
+        ))
+        .to_compile_error()
+        .into();
+    }
+    parsed_path
+        .options
+        .insert("delim".to_string(), DYN_DELIM_PLACEHOLDER.to_string());
+    let final_field_path = final_field_path(&parsed_path);
+    let segments: Vec<&str> = final_field_path.split(DYN_DELIM_PLACEHOLDER).collect();
+
+    let result_str = format!(
+        "{{{}\n[{}].join({})}}",
+        all_check_functions,
+        segments
+            .iter()
+            .map(|segment| format!("{:?}", segment))
+            .collect::<Vec<String>>()
+            .join(", "),
+        delim_expr
+    );
+    result_str.parse().unwrap()
+}
+
+/// The delimiter of the `path_dyn_delim!` segments replaced with the runtime delimiter.
+#[cfg(feature = "std")]
+const DYN_DELIM_PLACEHOLDER: &str = "\u{1f}";
+
+/// Returns a closure setting a value at the path in a `serde_json::Value`, so
+/// `path_setter_json!(TestStructParent::value_child.child_value_str)` returns
+/// `|root: &mut serde_json::Value, value: serde_json::Value|` creating the `value_child` object when needed
+/// and assigning the value to its `child_value_str` key.
+/// The intermediate values that aren't objects (including the root) are replaced with the empty objects.
+/// Available with the `serde_json` feature and requires the `serde_json` crate.
+#[cfg(feature = "serde_json")]
+#[proc_macro]
+pub fn path_setter_json(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    let segments: Vec<String> = converted_segments(&parsed_path.options, &parsed_path.field_path)
+        .iter()
+        .map(|segment| format!("{:?}", segment))
+        .collect();
+
+    let result_str = format!(
+        r#"{{{}
+            |root: &mut ::serde_json::Value, value: ::serde_json::Value| {{
+                let mut current = root;
+                for segment in [{}] {{
+                    if !current.is_object() {{
+                        *current = ::serde_json::Value::Object(::serde_json::Map::new());
+                    }}
+                    current = current
+                        .as_object_mut()
+                        .unwrap()
+                        .entry(segment)
+                        .or_insert(::serde_json::Value::Null);
+                }}
+                *current = value;
+            }}
+        }}"#,
+        all_check_functions,
+        segments.join(", ")
+    );
+    result_str.parse().unwrap()
+}
+
+/// Returns the first path relative to the second one on the same root struct, so
+/// `relative_path!(TestStructParent::value_child.child_value_str, TestStructParent::value_child)`
+/// returns `"child_value_str"`. The second path must be a strict prefix of the first one:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+///     pub value_num: u64,
+/// }
+///
+/// let s: &str = relative_path!(TestStructParent::value_str, TestStructParent::value_num);
+/// ```
+///
+/// Both paths must start with the same struct path as written, so the same named structs
+/// of the different modules are rejected:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// mod users {
+///     pub struct User {
+///         pub address: Address,
+///     }
+///
+///     pub struct Address {
+///         pub city: String,
+///     }
+/// }
+///
+/// mod accounts {
+///     pub struct User {
+///         pub address: super::users::Address,
+///     }
+/// }
+///
+/// let s: &str = relative_path!(users::User::address.city, accounts::User::address);
+/// ```
+#[proc_macro]
+pub fn relative_path(struct_path_stream: TokenStream) -> TokenStream {
+    let path_tokens: Vec<TokenTree> = struct_path_stream.into_iter().collect();
+    let options_pos = path_tokens
+        .iter()
+        .position(
+            |token_tree| matches!(token_tree, TokenTree::Punct(punct) if punct.as_char() == ';'),
+        )
+        .unwrap_or(path_tokens.len());
+    let comma_pos = match top_level_comma_positions(&path_tokens[..options_pos]).as_slice() {
+        [comma_pos] => *comma_pos,
+        _ => panic!("Expected two struct paths separated by a comma!"),
+    };
+    let parse_with_options = |struct_path_tokens: &[TokenTree]| {
+        parse_checked_path(
+            struct_path_tokens
+                .iter()
+                .chain(path_tokens[options_pos..].iter())
+                .cloned()
+                .collect(),
+        )
+    };
+    let (parsed_path, parsed_base_path) = match (
+        parse_with_options(&path_tokens[..comma_pos]),
+        parse_with_options(&path_tokens[comma_pos + 1..options_pos]),
+    ) {
+        (Ok(parsed_path), Ok(parsed_base_path)) => (parsed_path, parsed_base_path),
+        (Err(error), _) | (_, Err(error)) => return error.to_compile_error().into(),
+    };
+
+    let all_check_functions = format!(
+        "{}{}",
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options),
+        generate_checks_code_for(&parsed_base_path.found_structs, &parsed_base_path.options)
+    );
+    let segments = split_field_path(&parsed_path.field_path);
+    let base_segments = split_field_path(&parsed_base_path.field_path);
+    let root_name = &parsed_path.found_structs[0].0;
+    let base_root_name = &parsed_base_path.found_structs[0].0;
+
+    let result_str = if root_name != base_root_name {
+        format!(
+            "compile_error!(\"The paths have different roots: {} and {}\")",
+            root_name, base_root_name
+        )
+    } else if segments.len() <= base_segments.len()
+        || segments
+            .iter()
+            .zip(base_segments.iter())
+            .any(|((_, segment), (_, base_segment))| segment != base_segment)
+    {
+        format!(
+            "compile_error!(\"The path {} doesn't start with {}\")",
+            parsed_path.field_path, parsed_base_path.field_path
+        )
+    } else {
+        let relative_field_path = segments[base_segments.len()..]
+            .iter()
+            .enumerate()
+            .map(|(pos, (separator, segment))| {
+                if pos == 0 {
+                    segment.to_string()
+                } else {
+                    format!("{}{}", separator, segment)
+                }
+            })
+            .collect::<String>();
+        format!(
+            "{{{}\n{:?}}}",
+            all_check_functions,
+            apply_options(&parsed_path.options, relative_field_path)
+        )
+    };
+    result_str.parse().unwrap()
+}
+
+/// Asserts at compile time that the first path is a prefix of the second one (or equal to it) by their segments
+/// with the options applied (the indexed elements such as `items[0]` are scoped by `items`), so the scoped queries such as `assert_path_prefix!(User::address, User::address.city)`
+/// are validated:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+///     pub value_num: u64,
+/// }
+///
+/// assert_path_prefix!(TestStructParent::value_str, TestStructParent::value_num);
+/// ```
+///
+/// Both paths must start with the same struct path as written, as in `relative_path!`:
+///
+/// ```rust,compile_fail
+/// use struct_path::*;
+///
+/// mod users {
+///     pub struct User {
+///         pub address: String,
+///     }
+/// }
+///
+/// mod accounts {
+///     pub struct User {
+///         pub address: String,
+///     }
+/// }
+///
+/// assert_path_prefix!(users::User::address, accounts::User::address);
+/// ```
+#[proc_macro]
+pub fn assert_path_prefix(struct_path_stream: TokenStream) -> TokenStream {
+    let path_tokens: Vec<TokenTree> = struct_path_stream.into_iter().collect();
+    let options_pos = path_tokens
+        .iter()
+        .position(
+            |token_tree| matches!(token_tree, TokenTree::Punct(punct) if punct.as_char() == ';'),
+        )
+        .unwrap_or(path_tokens.len());
+    let comma_pos = match top_level_comma_positions(&path_tokens[..options_pos]).as_slice() {
+        [comma_pos] => *comma_pos,
+        _ => panic!("Expected two struct paths separated by a comma!"),
+    };
+    let parse_with_options = |struct_path_tokens: &[TokenTree]| {
+        parse_checked_path(
+            struct_path_tokens
+                .iter()
+                .chain(path_tokens[options_pos..].iter())
+                .cloned()
+                .collect(),
+        )
+    };
+    let (parsed_prefix_path, parsed_path) = match (
+        parse_with_options(&path_tokens[..comma_pos]),
+        parse_with_options(&path_tokens[comma_pos + 1..options_pos]),
+    ) {
+        (Ok(parsed_prefix_path), Ok(parsed_path)) => (parsed_prefix_path, parsed_path),
+        (Err(error), _) | (_, Err(error)) => return error.to_compile_error().into(),
+    };
+
+    let all_check_functions = format!(
+        "{}{}",
+        generate_checks_code_for(
+            &parsed_prefix_path.found_structs,
+            &parsed_prefix_path.options
+        ),
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options)
+    );
+    let prefix_segments =
+        converted_segments(&parsed_prefix_path.options, &parsed_prefix_path.field_path);
+    let segments = converted_segments(&parsed_path.options, &parsed_path.field_path);
+    let prefix_root_name = &parsed_prefix_path.found_structs[0].0;
+    let root_name = &parsed_path.found_structs[0].0;
+
+    let result_str = if prefix_root_name != root_name {
+        format!(
+            "compile_error!(\"The paths have different roots: {} and {}\");",
+            prefix_root_name, root_name
+        )
+    } else if segments.len() < prefix_segments.len()
+        || segments.iter().zip(prefix_segments.iter()).enumerate().any(
+            |(pos, (segment, prefix_segment))| {
+                // The last prefix segment also scopes the indexed elements such as `items[0]` of `items`
+                segment != prefix_segment
+                    && (pos + 1 < prefix_segments.len()
+                        || !segment
+                            .strip_prefix(prefix_segment.as_str())
+                            .is_some_and(|brackets| brackets.starts_with('[')))
+            },
+        )
+    {
+        format!(
+            "compile_error!(\"The path {} doesn't start with {}\");",
+            parsed_path.field_path, parsed_prefix_path.field_path
+        )
+    } else {
+        format!("const _: () = {{{}}};", all_check_functions)
+    };
+    result_str.parse().unwrap()
+}
+
+/// Appends the checked path to a base path, so `path_append!("orders", TestStructParent::value_child.child_value_str)`
+/// returns `"orders.value_child.child_value_str"`. The string literal bases produce `&str` literals,
+/// while the other base expressions produce `String`s by `format!`, such as `path_append!(base, TestStructParent::value_str)`.
+#[proc_macro]
+pub fn path_append(struct_path_stream: TokenStream) -> TokenStream {
+    let path_tokens: Vec<TokenTree> = struct_path_stream.into_iter().collect();
+    let comma_pos = match top_level_comma_positions(&path_tokens).first() {
+        Some(comma_pos) if *comma_pos > 0 => *comma_pos,
+        _ => panic!("Expected a base path expression followed by a comma!"),
+    };
+    let parsed_path =
+        match parse_checked_path(path_tokens[comma_pos + 1..].iter().cloned().collect()) {
+            Ok(parsed_path) => parsed_path,
+            Err(error) => return error.to_compile_error().into(),
+        };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    let delim = delim_option(&parsed_path.options);
+    let final_field_path = final_field_path(&parsed_path);
+
+    let result_str = match &path_tokens[..comma_pos] {
+        [TokenTree::Literal(lit)] if lit.to_string().starts_with('"') => {
+            format!(
+                "{{{}\n{:?}}}",
+                all_check_functions,
+                format!(
+                    "{}{}{}",
+                    string_literal_value(&lit.to_string()).unwrap(),
+                    delim,
+                    final_field_path
+                )
+            )
+        }
+        base_tokens => format!(
+            "{{{}\n::std::format!(\"{{}}{{}}\", {}, {:?})}}",
+            all_check_functions,
+            base_tokens.iter().cloned().collect::<TokenStream>(),
+            format!("{}{}", delim, final_field_path)
+        ),
+    };
+    result_str.parse().unwrap()
+}
+
+/// Returns the path of a `Vec` field for the push operations such as the MongoDB `$push`,
+/// so `push_path!(TestStructOrder::items)` returns `"items"`, additionally verifying the field is a `Vec`:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// let s: &str = push_path!(TestStructParent::value_str);
+/// ```
+#[proc_macro]
+pub fn push_path(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let mut all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    if check_option(&parsed_path.options) {
+        let (struct_name, struct_fields) = parsed_path.found_structs.last().unwrap();
+        all_check_functions.push_str(&format!(
+            r#"
+            {{
+                #[allow(dead_code, unused_variables, unreachable_patterns)]
+                #[cold]
+                fn _check_push(test_struct: &{}) {{
+                    {}
+                    let _: &Vec<_> = _t;
+                }}
+            }}
+            "#,
+            struct_name,
+            generate_field_access_code(
+                &format!("test_struct.{}", struct_fields[0]),
+                &[],
+                &parsed_path.options
+            )
+        ));
+    }
+    let result_str = format!(
+        "{{{}\n{:?}}}",
+        all_check_functions,
+        final_field_path(&parsed_path)
+    );
+    result_str.parse().unwrap()
+}
+
+/// Returns the path of a `Copy` field for the value-based query builders,
+/// so `path_copy!(TestStructParent::value_num)` returns `"value_num"`, additionally verifying the field is `Copy`:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_str: String,
+/// }
+///
+/// let s: &str = path_copy!(TestStructParent::value_str);
+/// ```
+#[proc_macro]
+pub fn path_copy(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let mut all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    if check_option(&parsed_path.options) {
+        let (struct_name, struct_fields) = parsed_path.found_structs.last().unwrap();
+        all_check_functions.push_str(&format!(
+            r#"
+            {{
+                #[allow(dead_code, unused_variables, unreachable_patterns)]
+                #[cold]
+                fn _check_copy(test_struct: &{}) {{
+                    fn _copy<T: Copy>(_: &T) {{}}
+                    {}
+                    _copy(_t);
+                }}
+            }}
+            "#,
+            struct_name,
+            generate_field_access_code(
+                &format!("test_struct.{}", struct_fields[0]),
+                &[],
+                &parsed_path.options
+            )
+        ));
+    }
+    let result_str = format!(
+        "{{{}\n{:?}}}",
+        all_check_functions,
+        final_field_path(&parsed_path)
+    );
+    result_str.parse().unwrap()
+}
+
+/// Returns the path with the asserted field type for the type-aware query builders,
+/// so `path_as!(User::created_at as u64)` returns `("created_at", "u64")`, additionally verifying the field type:
+///
+#[cfg_attr(not(feature = "no-checks"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "no-checks", doc = "```rust,ignore")]
+/// use struct_path::*;
+///
+/// pub struct TestStructParent {
+///     pub value_num: u64,
+/// }
+///
+/// let s: (&str, &str) = path_as!(TestStructParent::value_num as i32);
+/// ```
+#[proc_macro]
+pub fn path_as(struct_path_stream: TokenStream) -> TokenStream {
+    let mut path_tokens: Vec<TokenTree> = struct_path_stream.into_iter().collect();
+    let as_pos = path_tokens
+        .iter()
+        .position(|token_tree| matches!(token_tree, TokenTree::Ident(id) if id.to_string() == "as"))
+        .unwrap_or_else(|| {
+            panic!("Expected a field type after the struct path as `path as Type`!")
+        });
+    let type_end = path_tokens
+        .iter()
+        .position(
+            |token_tree| matches!(token_tree, TokenTree::Punct(punct) if punct.as_char() == ';'),
+        )
+        .unwrap_or(path_tokens.len());
+    let type_tokens: Vec<TokenTree> = path_tokens.drain(as_pos..type_end).skip(1).collect();
+    if type_tokens.is_empty() {
+        panic!("Expected a field type after the struct path as `path as Type`!");
+    }
+    let type_name = compact_type_name(&type_tokens);
+
+    let parsed_path = match parse_checked_path(path_tokens.into_iter().collect()) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let mut all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    if check_option(&parsed_path.options) {
+        let (struct_name, struct_fields) = parsed_path.found_structs.last().unwrap();
+        all_check_functions.push_str(&format!(
+            r#"
+            {{
+                #[allow(dead_code, unused_variables, unreachable_patterns)]
+                #[cold]
+                fn _check_as(test_struct: &{}) {{
+                    {}
+                    let _: &{} = _t;
+                }}
+            }}
+            "#,
+            struct_name,
+            generate_field_access_code(
+                &format!("test_struct.{}", struct_fields[0]),
+                &[],
+                &parsed_path.options
+            ),
+            type_name
+        ));
+    }
+    let result_str = format!(
+        "{{{}\n({:?}, {:?})}}",
+        all_check_functions,
+        final_field_path(&parsed_path),
+        type_name
+    );
+    result_str.parse().unwrap()
+}
+
+/// Returns the type tokens as a string without the spaces around the punctuation, such as `Vec<u8>`.
+fn compact_type_name(type_tokens: &[TokenTree]) -> String {
+    let mut type_name = String::new();
+    let mut prev_is_word = false;
+    for token_tree in type_tokens {
+        let is_word = matches!(token_tree, TokenTree::Ident(_) | TokenTree::Literal(_));
+        if is_word && prev_is_word {
+            type_name.push(' ');
+        }
+        match token_tree {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                let group_tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+                type_name.push_str(open);
+                type_name.push_str(&compact_type_name(&group_tokens));
+                type_name.push_str(close);
+            }
+            TokenTree::Punct(punct) if punct.as_char() == ',' || punct.as_char() == ';' => {
+                type_name.push(punct.as_char());
+                type_name.push(' ');
+            }
+            others => type_name.push_str(&others.to_string()),
+        }
+        prev_is_word = is_word;
+    }
+    type_name
+}
+
+/// Returns the path as an owned `String` for appending the runtime keys, so
+/// `path_string!(TestStructParent::value_child.child_value_str)` returns `String::from("value_child.child_value_str")`.
+/// Available with the `std` feature (enabled by default).
+#[cfg(feature = "std")]
+#[proc_macro]
+pub fn path_string(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    let final_field_path = final_field_path(&parsed_path);
+    let result_str = format!(
+        "{{{}\n::std::string::String::from({:?})}}",
+        all_check_functions, final_field_path
+    );
+    result_str.parse().unwrap()
+}
+
+/// Declares the `PathDoc` value of `path_doc!` pairing the final path with its generated description:
+/// `pub struct PathDoc { pub path: &'static str, pub doc: &'static str }`.
+/// Invoke it once per crate (or module) before the first `path_doc!` call.
+#[proc_macro]
+pub fn define_path_doc(define_stream: TokenStream) -> TokenStream {
+    define_static_str_struct(
+        define_stream,
+        "define_path_doc",
+        "PathDoc",
+        &["path", "doc"],
+    )
+}
+
+/// Returns the path with a human-readable doc assembled from the segments for the generated API clients
+/// as the `PathDoc` defined by `define_path_doc!`, so `path_doc!(TestStructParent::value_child.child_value_str)`
+/// returns `PathDoc { path: "value_child.child_value_str", doc: "Field child_value_str of ValueChild within TestStructParent" }`.
+/// The struct names of the nested fields are only known in the comma form such as
+/// `path_doc!(TestStructParent::value_child, TestStructChild::child_value_str)`,
+/// otherwise they are named after the parent fields in `PascalCase`.
+#[proc_macro]
+pub fn path_doc(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    let final_field_path = final_field_path(&parsed_path);
+    let root_struct_name = simple_struct_name(&parsed_path.found_structs[0].0);
+    let (last_struct_name, last_struct_fields) = parsed_path.found_structs.last().unwrap();
+    let last_struct_segments = split_field_path(&last_struct_fields[0]);
+    let field_owner_name = match last_struct_segments.as_slice() {
+        [.., (_, parent_segment), _] => {
+            let (parent_field_name, _) = split_segment_brackets(parent_segment);
+            let (parent_field_name, _) = split_segment_annotation(parent_field_name);
+            parent_field_name
+                .trim_start_matches("r#")
+                .from_case(Case::Snake)
+                .to_case(Case::Pascal)
+        }
+        _ => simple_struct_name(last_struct_name).to_string(),
+    };
+    let field_doc = if field_owner_name == root_struct_name {
+        format!(
+            "Field {} of {}",
+            last_field_name(&last_struct_fields[0]),
+            field_owner_name
+        )
+    } else {
+        format!(
+            "Field {} of {} within {}",
+            last_field_name(&last_struct_fields[0]),
+            field_owner_name,
+            root_struct_name
+        )
+    };
+    let result_str = format!(
+        "{{{}\nPathDoc {{ path: {:?}, doc: {:?} }}}}",
+        all_check_functions, final_field_path, field_doc
+    );
+    result_str.parse().unwrap()
+}
+
+/// Returns the separate segments of a single path, so `path_vec!(TestStructParent::value_child.child_value_str)`
+/// returns `vec!["value_child", "child_value_str"]` for the APIs taking `&[&str]`, unlike `paths!`
+/// returning a whole path per field. The segments are converted with the options, while `delim` is ignored.
+/// Available with the `std` feature (enabled by default).
+#[cfg(feature = "std")]
+#[proc_macro]
+pub fn path_vec(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    let field_path = tagged_field_path(
+        &parsed_path.options,
+        &parsed_path.found_structs[0].0,
+        &parsed_path.field_path,
+    );
+    let segments = converted_segments(&parsed_path.options, &field_path)
+        .iter()
+        .map(|segment| format!("{:?}", segment))
+        .collect::<Vec<String>>();
+    let result_str = format!(
+        "{{{}\n::std::vec![{}]}}",
+        all_check_functions,
+        segments.join(",")
+    );
+    result_str.parse().unwrap()
+}
+
+/// Returns the path as a shared `Arc<str>`, so `path_arc!(TestStructParent::value_child.child_value_str)`
+/// returns `Arc::<str>::from("value_child.child_value_str")`.
+/// Available with the `std` feature (enabled by default).
+#[cfg(feature = "std")]
+#[proc_macro]
+pub fn path_arc(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    let final_field_path = final_field_path(&parsed_path);
+    let result_str = format!(
+        "{{{}\n::std::sync::Arc::<str>::from({:?})}}",
+        all_check_functions, final_field_path
+    );
+    result_str.parse().unwrap()
+}
+
+/// Returns an accessor with the path and the getter/setter closures for the field, so
+/// `path_accessor!(TestStructParent::value_child.child_value_str)` returns a struct with
+/// the `path` field `"value_child.child_value_str"`, the `get` closure `|s: &TestStructParent| -> &String`
+/// and the `set` closure `|s: &mut TestStructParent, v: String|`.
+///
+/// The closures are called as `(accessor.get)(&value)` and `(accessor.set)(&mut value, new_value)`.
+/// Only the plain field paths are supported, without the `~` and bracket segments.
+#[proc_macro]
+pub fn path_accessor(struct_path_stream: TokenStream) -> TokenStream {
+    let parsed_path = match parse_checked_path(struct_path_stream) {
+        Ok(parsed_path) => parsed_path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let all_check_functions =
+        generate_checks_code_for(&parsed_path.found_structs, &parsed_path.options);
+    if split_field_path(&parsed_path.field_path)
+        .iter()
+        .any(|(separator, segment)| {
+            *separator != "."
+                || segment.contains('[')
+                || segment.contains('@')
+                || segment.starts_with('"')
+        })
+    {
+        panic!(
+            "Accessors are supported only for the plain field paths: {}",
+            parsed_path.field_path
+        );
+    }
+    let struct_name = &parsed_path.found_structs[0].0;
+    let result_str = format!(
+        r#"{{{}
+            struct PathAccessor<G, S> {{
+                path: &'static str,
+                get: G,
+                set: S,
+            }}
+
+            fn _path_accessor<T, V, G: Fn(&T) -> &V, S: Fn(&mut T, V)>(
+                path: &'static str,
+                get: G,
+                set: S,
+            ) -> PathAccessor<G, S> {{
+                PathAccessor {{ path, get, set }}
+            }}
+
+            _path_accessor(
+                {:?},
+                |s: &{}| &s.{},
+                |s: &mut {}, v| s.{} = v,
+            )
+        }}"#,
+        all_check_functions,
+        final_field_path(&parsed_path),
+        struct_name,
+        parsed_path.field_path,
+        struct_name,
+        parsed_path.field_path
+    );
+    result_str.parse().unwrap()
+}
+
+/// Injects the root struct into the inner path macros invocations that omit it,
+/// so `path!(.value_child.child_value_str)` and `paths!({ value_str, value_num })` inside
+/// `with_root!(TestStructParent => ...)` are expanded as `path!(TestStructParent::value_child.child_value_str)`
+/// and `paths!(TestStructParent::{ value_str, value_num })`.
+///
+/// The contents of `{ ... }` are emitted as is (so they can be items or statements),
+/// while `( ... )` and `[ ... ]` are emitted as tuple and array expressions.
+#[proc_macro]
+pub fn with_root(with_root_stream: TokenStream) -> TokenStream {
+    let mut root_tokens: Vec<TokenTree> = Vec::new();
+    let mut with_root_iter = with_root_stream.into_iter();
+    let mut arrow_opened = false;
+
+    for token_tree in with_root_iter.by_ref() {
+        match token_tree {
+            TokenTree::Punct(punct) if punct == '=' && punct.spacing() == Spacing::Joint => {
+                arrow_opened = true;
+            }
+            TokenTree::Punct(punct) if punct == '>' && arrow_opened => {
+                break;
+            }
+            others if !arrow_opened => root_tokens.push(others),
+            others => {
+                panic!("Unexpected input for root struct definition: {:?}", others)
+            }
+        }
+    }
+    if root_tokens.is_empty() {
+        panic!("Expected a root struct definition followed by =>!");
+    }
+    let root: TokenStream = root_tokens.into_iter().collect();
+
+    match with_root_iter.next() {
+        Some(TokenTree::Group(group)) if with_root_iter.next().is_none() => {
+            let rewritten_stream = inject_root(&root, group.stream());
+            match group.delimiter() {
+                Delimiter::Brace => rewritten_stream,
+                delimiter => TokenTree::Group(Group::new(delimiter, rewritten_stream)).into(),
+            }
+        }
+        _ => panic!("Expected a single group of path macros invocations after =>!"),
+    }
+}
+
+/// Defines the `macro_rules!` wrappers of the path macros with the baked-in default options,
+/// so `define_path_macros!(camel_path => path, camel_paths => paths; case = "camel")` defines
+/// `camel_path!(TestStructParent::value_child.child_value_str)` returning `"valueChild.childValueStr"`.
+///
+/// The options specified explicitly in the wrappers invocations override the defaults.
+/// The wrappers follow the `macro_rules!` scoping, so they are available after the definition.
+#[proc_macro]
+pub fn define_path_macros(define_stream: TokenStream) -> TokenStream {
+    let define_tokens: Vec<TokenTree> = define_stream.into_iter().collect();
+    let options_pos = define_tokens
+        .iter()
+        .position(
+            |token_tree| matches!(token_tree, TokenTree::Punct(punct) if punct.as_char() == ';'),
+        )
+        .unwrap_or(define_tokens.len());
+    let default_options: TokenStream = define_tokens
+        .iter()
+        .skip(options_pos + 1)
+        .cloned()
+        .collect();
+
+    let mut definitions_tokens = define_tokens[..options_pos].to_vec();
+    definitions_tokens.push(TokenTree::Punct(proc_macro::Punct::new(
+        ',',
+        Spacing::Alone,
+    )));
+    let mut macros_code = String::new();
+    let mut start = 0;
+    for end in top_level_comma_positions(&definitions_tokens) {
+        match &definitions_tokens[start..end] {
+            [TokenTree::Ident(wrapper_name), TokenTree::Punct(eq), TokenTree::Punct(gt), TokenTree::Ident(macro_name)]
+                if eq.as_char() == '=' && gt.as_char() == '>' =>
+            {
+                if !ROOT_AWARE_MACROS.contains(&macro_name.to_string().as_str()) {
+                    panic!(
+                        "Unsupported path macro to define a wrapper for: {}",
+                        macro_name
+                    );
+                }
+                macros_code.push_str(&format!(
+                    r#"
+                    macro_rules! {} {{
+                        ($($path_tokens:tt)*) => {{
+                            ::struct_path::{}!($($path_tokens)* ;; {})
+                        }};
+                    }}
+                    "#,
+                    wrapper_name, macro_name, default_options
+                ));
+            }
+            [] if end == definitions_tokens.len() - 1 && start > 0 => {}
+            others => panic!(
+                "Expected the wrapper definitions as `wrapper_name => path_macro`: {:?}",
+                others
+            ),
+        }
+        start = end + 1;
+    }
+    if macros_code.is_empty() {
+        panic!("Expected at least one wrapper definition!");
+    }
+
+    macros_code.parse().unwrap()
+}
+
+const ROOT_AWARE_MACROS: [&str; 21] = [
+    "path",
+    "paths",
+    "at_depth",
+    "paths_json",
+    "field_mask",
+    "path_keyed",
+    "path_arc",
+    "path_string",
+    "path_vec",
+    "path_doc",
+    "paths_grouped",
+    "phf_paths",
+    "path_dyn_delim",
+    "path_setter_json",
+    "path_exact_depth",
+    "segment_count",
+    "push_path",
+    "path_copy",
+    "paths_btree",
+    "paths_map",
+    "rename_map",
+];
+
+fn inject_root(root: &TokenStream, stream: TokenStream) -> TokenStream {
+    let mut result_tokens: Vec<TokenTree> = Vec::new();
+    let mut macro_name_opened = false;
+
+    for token_tree in stream.into_iter() {
+        match token_tree {
+            TokenTree::Punct(punct) if punct == '!' => {
+                macro_name_opened = matches!(
+                    result_tokens.last(),
+                    Some(TokenTree::Ident(id)) if ROOT_AWARE_MACROS.contains(&id.to_string().as_str())
+                );
+                result_tokens.push(TokenTree::Punct(punct));
+            }
+            TokenTree::Group(group)
+                if macro_name_opened && group.delimiter() == Delimiter::Parenthesis =>
+            {
+                macro_name_opened = false;
+                let mut macro_tokens = group.stream().into_iter().peekable();
+                let rooted_stream: TokenStream = match macro_tokens.peek() {
+                    Some(TokenTree::Punct(punct)) if punct.as_char() == '.' => {
+                        macro_tokens.next();
+                        format!("{} :: {}", root, macro_tokens.collect::<TokenStream>())
+                            .parse()
+                            .unwrap()
+                    }
+                    Some(TokenTree::Group(inner_group))
+                        if inner_group.delimiter() == Delimiter::Brace =>
+                    {
+                        format!("{} :: {}", root, macro_tokens.collect::<TokenStream>())
+                            .parse()
+                            .unwrap()
+                    }
+                    _ => macro_tokens.collect(),
+                };
+                let mut rooted_group = Group::new(Delimiter::Parenthesis, rooted_stream);
+                rooted_group.set_span(group.span());
+                result_tokens.push(TokenTree::Group(rooted_group));
+            }
+            TokenTree::Group(group) => {
+                macro_name_opened = false;
+                let mut rewritten_group =
+                    Group::new(group.delimiter(), inject_root(root, group.stream()));
+                rewritten_group.set_span(group.span());
+                result_tokens.push(TokenTree::Group(rewritten_group));
+            }
+            others => {
+                macro_name_opened = false;
+                result_tokens.push(others);
+            }
+        }
+    }
+
+    result_tokens.into_iter().collect()
+}
+
+/// Whether the fields verification is enabled, `check = false` skips it for the roots
+/// that can't be verified such as the generic type parameters.
 #[inline]
-fn apply_options(options: &HashMap<String, String>, field_path: String) -> String {
-    let delim = options
+fn check_option(options: &HashMap<String, String>) -> bool {
+    if cfg!(feature = "no-checks") {
+        return false;
+    }
+    match options.get("check").map(|s| s.as_str()) {
+        Some("true") | None => true,
+        Some("false") => false,
+        Some(others) => panic!("Unknown check option value is specified: {}", others),
+    }
+}
+
+#[inline]
+fn generate_checks_code_for(
+    found_structs: &[(String, Vec<String>)],
+    options: &HashMap<String, String>,
+) -> String {
+    generate_checks_for(found_structs, &[], options).to_string()
+}
+
+/// Generates the fields verification code with the fields spanned at the macro input tokens
+/// from the field spans of every struct field path, so the missing fields are reported at the user's tokens.
+fn generate_checks_for(
+    found_structs: &[(String, Vec<String>)],
+    field_spans: &[Vec<FieldSpans>],
+    options: &HashMap<String, String>,
+) -> proc_macro2::TokenStream {
+    if !check_option(options) {
+        return proc_macro2::TokenStream::new();
+    }
+
+    let mut all_check_functions = proc_macro2::TokenStream::new();
+    // The leaf field verification options such as `length` apply only to the last struct,
+    // since the previous ones of `path!(A::b, B::c)` are the intermediate fields
+    let mut intermediate_options = options.clone();
+    intermediate_options.remove("length");
+
+    for (struct_pos, (struct_name, struct_fields)) in found_structs.iter().enumerate() {
+        let options = if struct_pos + 1 == found_structs.len() {
+            options
+        } else {
+            &intermediate_options
+        };
+        for (field_pos, field_path) in struct_fields.iter().enumerate() {
+            let path_spans: &[(String, proc_macro2::Span)] = field_spans
+                .get(struct_pos)
+                .and_then(|struct_field_spans| struct_field_spans.get(field_pos))
+                .map(|path_spans| path_spans.as_slice())
+                .unwrap_or_default();
+            if let Some((enum_name, variant_name)) = enum_variant_root(struct_name) {
+                all_check_functions.extend(generate_enum_checks_for(
+                    enum_name,
+                    variant_name,
+                    field_path,
+                    path_spans,
+                    options,
+                ));
+                continue;
+            }
+            let struct_type = parse_code(struct_name);
+            let field_access_code = generate_field_access_code(
+                &format!("test_struct.{}", field_path),
+                path_spans,
+                options,
+            );
+            all_check_functions.extend(quote! {
+                {
+                    #[allow(dead_code, unused_variables, unreachable_patterns)]
+                    #[cold]
+                    fn _check_sp(test_struct: &#struct_type) {
+                        #field_access_code
+                    }
+                }
+            });
+        }
+    }
+    all_check_functions
+}
+
+#[inline]
+fn generate_enum_checks_code_for(
+    enum_name: &str,
+    variant_name: &str,
+    field_path: &str,
+    options: &HashMap<String, String>,
+) -> String {
+    generate_enum_checks_for(enum_name, variant_name, field_path, &[], options).to_string()
+}
+
+fn generate_enum_checks_for(
+    enum_name: &str,
+    variant_name: &str,
+    field_path: &str,
+    path_spans: &[(String, proc_macro2::Span)],
+    options: &HashMap<String, String>,
+) -> proc_macro2::TokenStream {
+    if !check_option(options) {
+        return proc_macro2::TokenStream::new();
+    }
+
+    let mut path_spans = FieldSpansCursor::new(path_spans);
+    let (variant_field, nested_field_path) =
+        field_path.split_at(split_field_path(field_path)[0].1.len());
+    let variant_field = split_segment_annotation(variant_field).0;
+    let enum_type = parse_code(enum_name);
+    let variant_name = proc_macro2::Ident::new(variant_name, proc_macro2::Span::call_site());
+    let variant_pattern = variant_field_pattern(variant_field, &mut path_spans);
+    let field_access_code = generate_field_access_code(
+        &format!("_v{}", nested_field_path),
+        path_spans.remaining(),
+        options,
+    );
+    quote! {
+        {
+            #[allow(dead_code, unused_variables, irrefutable_let_patterns, unreachable_patterns)]
+            #[cold]
+            fn _check_sp(test_enum: &#enum_type) {
+                if let #enum_type::#variant_name #variant_pattern = test_enum {
+                    #field_access_code
+                }
+            }
+        }
+    }
+}
+
+/// Parses the generated code such as the struct types and the brackets contents.
+#[inline]
+fn parse_code(code: &str) -> proc_macro2::TokenStream {
+    code.parse()
+        .unwrap_or_else(|_| panic!("Unexpected struct path code: {}", code))
+}
+
+/// Takes the spans of the field path tokens in their order, so the same field names
+/// of the different segments get the spans of their own tokens.
+struct FieldSpansCursor<'a> {
+    path_spans: &'a [(String, proc_macro2::Span)],
+}
+
+impl<'a> FieldSpansCursor<'a> {
+    fn new(path_spans: &'a [(String, proc_macro2::Span)]) -> Self {
+        FieldSpansCursor { path_spans }
+    }
+
+    /// Returns the span of the next token with the name, or the call site for the names missing in the input.
+    fn take(&mut self, name: &str) -> proc_macro2::Span {
+        match self
+            .path_spans
+            .iter()
+            .position(|(token_name, _)| token_name == name)
+        {
+            Some(pos) => {
+                let span = self.path_spans[pos].1;
+                self.path_spans = &self.path_spans[pos + 1..];
+                span
+            }
+            None => proc_macro2::Span::call_site(),
+        }
+    }
+
+    fn remaining(&self) -> &'a [(String, proc_macro2::Span)] {
+        self.path_spans
+    }
+}
+
+/// The field identifier (including the raw identifiers such as `r#type`) or the tuple index with the span.
+fn field_member(field_name: &str, span: proc_macro2::Span) -> proc_macro2::TokenStream {
+    if let Ok(tuple_index) = field_name.parse::<usize>() {
+        let mut tuple_index = proc_macro2::Literal::usize_unsuffixed(tuple_index);
+        tuple_index.set_span(span);
+        return quote!(#tuple_index);
+    }
+    match field_name.strip_prefix("r#") {
+        Some(raw_field_name) => {
+            let field_ident = proc_macro2::Ident::new_raw(raw_field_name, span);
+            quote!(#field_ident)
+        }
+        None => {
+            let field_ident = proc_macro2::Ident::new(field_name, span);
+            quote!(#field_ident)
+        }
+    }
+}
+
+/// The pattern binding the variant field as `_v`, such as `{ progress: _v, .. }` or `(_, _v, ..)` for the tuple variants.
+fn variant_field_pattern(
+    variant_field: &str,
+    path_spans: &mut FieldSpansCursor,
+) -> proc_macro2::TokenStream {
+    let span = path_spans.take(variant_field);
+    match variant_field.parse::<usize>() {
+        Ok(tuple_index) => {
+            let skipped_fields = (0..tuple_index).map(|_| quote!(_,));
+            quote!((#(#skipped_fields)* _v, ..))
+        }
+        Err(_) => {
+            let field_member = field_member(variant_field, span);
+            quote!({ #field_member: _v, .. })
+        }
+    }
+}
+
+#[inline]
+fn generate_field_access_code(
+    access_path: &str,
+    path_spans: &[(String, proc_macro2::Span)],
+    options: &HashMap<String, String>,
+) -> proc_macro2::TokenStream {
+    let strict = options.get("strict").map(|s| s.as_str()) == Some("true");
+    let mut path_spans = FieldSpansCursor::new(path_spans);
+    let mut field_access_code = proc_macro2::TokenStream::new();
+    let mut access_expr = proc_macro2::TokenStream::new();
+    let mut enum_variant: Option<&str> = None;
+    for (segment_pos, (separator, segment)) in split_field_path(access_path).into_iter().enumerate()
+    {
+        // The root of the access path such as `test_struct` isn't a field of the input
+        if segment_pos == 0 {
+            let root_ident = proc_macro2::Ident::new(segment, proc_macro2::Span::call_site());
+            access_expr = quote!(#root_ident);
+            continue;
+        }
+        // The literal segments aren't struct fields, so the check stops at them
+        if segment.starts_with('"') {
+            break;
+        }
+        for step in separator.chars() {
+            match step {
+                // Traversing the first item keeps the check valid for any `Iter`-based field
+                '~' => {
+                    if strict {
+                        field_access_code.extend(quote!(let _: &Option<_> = &#access_expr;));
+                    }
+                    access_expr = quote!(#access_expr.iter().next().unwrap());
+                }
+                // Reaching the `Ok` value without the `Debug` bound of `Result::unwrap` on the error type
+                '?' => access_expr = quote!(#access_expr.as_ref().ok().unwrap()),
+                '*' => access_expr = quote!((*#access_expr)),
+                // The slices indexing rejects the other `Iter`-based fields such as `Option`
+                '#' => access_expr = quote!(#access_expr[..][0]),
+                _ => {}
+            }
+        }
+        // The map key segments aren't struct fields, so the check continues with any of the values
+        if segment.starts_with('{') {
+            access_expr = quote!(#access_expr.values().next().unwrap());
+            continue;
+        }
+        let (field_name, brackets) = split_segment_brackets(segment);
+        let field_name = split_segment_annotation(field_name).0;
+        match enum_variant.take() {
+            // The variant field is bound by the matching enum variant
+            Some(enum_variant) => {
+                let enum_variant_path = enum_variant.split("::").map(|variant_path_name| {
+                    proc_macro2::Ident::new(variant_path_name, path_spans.take(variant_path_name))
+                });
+                let enum_variant_path = quote!(#(#enum_variant_path)::*);
+                let variant_pattern = variant_field_pattern(field_name, &mut path_spans);
+                access_expr = quote! {
+                    (*match &#access_expr { #enum_variant_path #variant_pattern => _v, _ => unreachable!() })
+                };
+            }
+            None => {
+                let field_member = field_member(field_name, path_spans.take(field_name));
+                access_expr = quote!(#access_expr.#field_member);
+            }
+        }
+        enum_variant = split_segment_enum_variant(segment).1;
+        for bracket in brackets {
+            if bracket == POSITIONAL_OPERATOR || bracket == WILDCARD_INDEX {
+                access_expr = quote!(#access_expr[0]);
+            } else {
+                let bracket_code = parse_code(bracket);
+                if enum_key_variant(bracket).is_some() {
+                    access_expr = quote!(#access_expr[&#bracket_code]);
+                } else {
+                    access_expr = quote!(#access_expr[#bracket_code]);
+                }
+            }
+            if bracket.contains("..") {
+                access_expr = quote!(#access_expr[0]);
+            }
+        }
+    }
+    if length_segment(options).is_some() {
+        field_access_code.extend(quote!(let _: &str = &#access_expr;));
+    }
+    field_access_code.extend(quote!(let _t = &#access_expr;));
+    field_access_code
+}
+
+/// Splits the field path into the segments by `.`, `~`, `?`, `*` and `#` outside the brackets,
+/// returning every segment with the separator preceding it (`.` for the first one).
+/// The consecutive separators such as `~*` are the multi-step separators of a single segment.
+fn split_field_path(field_path: &str) -> Vec<(&str, &str)> {
+    let mut segments = Vec::new();
+    let mut brackets_counter = 0;
+    let mut literal_opened = false;
+    let mut escaped = false;
+    let mut separator = ".";
+    let mut separator_start = 0;
+    let mut segment_start = 0;
+    for (pos, c) in field_path.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if literal_opened => escaped = true,
+            '"' => literal_opened = !literal_opened,
+            _ if literal_opened => {}
+            '[' | '{' => brackets_counter += 1,
+            ']' | '}' => brackets_counter -= 1,
+            // The raw identifiers such as `r#type` aren't split
+            '#' if &field_path[segment_start..pos] == "r" => {}
+            '.' | '~' | '?' | '*' | '#' if brackets_counter == 0 => {
+                if pos > 0 && pos == segment_start {
+                    separator = &field_path[separator_start..pos + 1];
+                } else {
+                    segments.push((separator, &field_path[segment_start..pos]));
+                    separator = &field_path[pos..pos + 1];
+                    separator_start = pos;
+                }
+                segment_start = pos + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push((separator, &field_path[segment_start..]));
+    segments
+}
+
+/// Returns the key of the map values segments such as `{*}` or `{"$key"}`, emitted as is.
+#[inline]
+fn map_key_segment(segment: &str) -> Option<String> {
+    let map_key = segment.strip_prefix('{')?.strip_suffix('}')?;
+    match map_key {
+        WILDCARD_INDEX => Some(WILDCARD_INDEX.to_string()),
+        _ => string_literal_value(map_key),
+    }
+}
+
+/// Splits the field name such as `http_url@raw` into the name and the segment annotation.
+#[inline]
+fn split_segment_annotation(field_name: &str) -> (&str, Option<&str>) {
+    if field_name.starts_with('"') {
+        return (field_name, None);
+    }
+    match field_name.split_once('@') {
+        Some((field_name, annotation)) => (field_name, Some(annotation)),
+        None => (field_name, None),
+    }
+}
+
+/// Returns the last field name of the field path without the indices and the annotations.
+fn last_field_name(field_path: &str) -> String {
+    let (_, segment) = split_field_path(field_path).pop().unwrap();
+    if segment.starts_with('"') {
+        return string_literal_value(segment).unwrap();
+    }
+    let (field_name, _) = split_segment_brackets(segment);
+    let (field_name, _) = split_segment_annotation(field_name);
+    field_name.to_string()
+}
+
+/// Splits the segment such as `state::MachineState::Running` into the field and its enum variant path.
+fn split_segment_enum_variant(segment: &str) -> (&str, Option<&str>) {
+    if segment.starts_with('"') {
+        return (segment, None);
+    }
+    let mut brackets_counter = 0;
+    for (pos, c) in segment.char_indices() {
+        match c {
+            '[' => brackets_counter += 1,
+            ']' => brackets_counter -= 1,
+            ':' if brackets_counter == 0 => return (&segment[..pos], Some(&segment[pos + 2..])),
+            _ => {}
+        }
+    }
+    (segment, None)
+}
+
+/// Splits the segment such as `items[1..3]` into the field name and the brackets contents,
+/// skipping the enum variant path of the segment.
+fn split_segment_brackets(segment: &str) -> (&str, Vec<&str>) {
+    if segment.starts_with('"') {
+        return (segment, Vec::new());
+    }
+    let segment = split_segment_enum_variant(segment).0;
+    match segment.find('[') {
+        Some(pos) => (
+            &segment[..pos],
+            segment[pos + 1..segment.len() - 1].split("][").collect(),
+        ),
+        None => (segment, Vec::new()),
+    }
+}
+
+#[inline]
+fn final_field_path(parsed_path: &ParsedPath) -> String {
+    let options = &parsed_path.options;
+    let root_struct_name = &parsed_path.found_structs[0].0;
+    let rooted_field_path = |field_path: String| {
+        if options.get("root_first").map(|s| s.as_str()) == Some("true") {
+            format!("{}.{}", root_segment(root_struct_name), field_path)
+        } else if let Some(root_segment) = included_root_segment(options, root_struct_name) {
+            format!("{}.{}", root_segment, field_path)
+        } else {
+            field_path
+        }
+    };
+    let final_path = match options.get("boundary") {
+        // The struct boundaries of `path!(A::b, B::c)` are marked as `b->c` instead of the delimiter
+        Some(boundary) => {
+            if let Some(format_value) = options.get("format") {
+                panic!(
+                    "The boundary option can't be combined with the format: {}",
+                    format_value
+                );
+            }
+            parsed_path
+                .found_structs
+                .iter()
+                .enumerate()
+                .map(|(struct_pos, (struct_name, struct_fields))| {
+                    if struct_pos == 0 {
+                        apply_versioned_options(
+                            options,
+                            rooted_field_path(tagged_field_path(
+                                options,
+                                struct_name,
+                                &struct_fields[0],
+                            )),
+                        )
+                    } else {
+                        apply_options(options, struct_fields[0].clone())
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(boundary)
+        }
+        None => apply_versioned_options(
+            options,
+            rooted_field_path(tagged_field_path(
+                options,
+                root_struct_name,
+                &parsed_path.field_path,
+            )),
+        ),
+    };
+    apply_template(
+        options,
+        root_struct_name,
+        finalize_path(options, final_path),
+    )
+}
+
+/// The root segment with `include_root = true`: the struct name as is, or in snake case
+/// when a case is specified, so it is converted the same way as the fields.
+#[inline]
+fn included_root_segment(options: &HashMap<String, String>, struct_name: &str) -> Option<String> {
+    match options.get("include_root").map(|s| s.as_str()) {
+        Some("true") if options.contains_key("case") => Some(root_segment(struct_name)),
+        Some("true") => Some(simple_struct_name(struct_name).to_string()),
+        Some("false") | None => None,
+        Some(others) => panic!("Unknown include_root option value is specified: {}", others),
+    }
+}
+
+#[inline]
+fn root_segment(struct_name: &str) -> String {
+    simple_struct_name(struct_name).to_case(Case::Snake)
+}
+
+#[inline]
+fn simple_struct_name(struct_name: &str) -> &str {
+    let struct_name = struct_type_name(struct_name);
+    struct_name
+        .rsplit_once("::")
+        .map(|(_, simple_name)| simple_name)
+        .unwrap_or(struct_name)
+}
+
+/// Returns the enum name and the variant name of the roots such as `TestPair::Pair` in `path!(TestPair::Pair::1)`,
+/// told apart from the module paths of the structs by the capitalized enum name.
+#[inline]
+fn enum_variant_root(struct_name: &str) -> Option<(&str, &str)> {
+    let (enum_name, variant_name) = struct_name.rsplit_once("::")?;
+    let is_capitalized = |name: &str| name.starts_with(|c: char| c.is_uppercase());
+    if is_capitalized(simple_struct_name(enum_name)) && is_capitalized(variant_name) {
+        Some((enum_name, variant_name))
+    } else {
+        None
+    }
+}
+
+/// Prepends the variant name of the enum roots as the externally-tagged serde representation,
+/// unless `tagged = false` is specified.
+#[inline]
+fn tagged_field_path(
+    options: &HashMap<String, String>,
+    struct_name: &str,
+    field_path: &str,
+) -> String {
+    match enum_variant_root(struct_name) {
+        Some((_, variant_name)) => match options.get("tagged").map(|s| s.as_str()) {
+            Some("true") | None => format!("{}@raw.{}", variant_name, field_path),
+            Some("false") => field_path.to_string(),
+            Some(others) => panic!("Unknown tagged option value is specified: {}", others),
+        },
+        None => field_path.to_string(),
+    }
+}
+
+/// The struct name without the turbofish generic arguments such as `Container::<u64>`.
+#[inline]
+fn struct_type_name(struct_name: &str) -> &str {
+    struct_name
+        .split_once("::<")
+        .map(|(struct_type_name, _)| struct_type_name)
+        .unwrap_or(struct_name)
+}
+
+#[inline]
+fn delim_option(options: &HashMap<String, String>) -> &str {
+    options
         .get("delim")
         .as_ref()
         .map(|s| s.as_str())
-        .unwrap_or_else(|| ".");
-    let case = options.get("case");
-    field_path
-        .split('.')
-        .map(|field_name| {
-            if let Some(case_value) = case {
-                match case_value.as_str() {
-                    "camel" => field_name.from_case(Case::Snake).to_case(Case::Camel),
-                    "pascal" => field_name.from_case(Case::Snake).to_case(Case::Pascal),
-                    another => panic!("Unknown case is specified: {}", another),
+        .unwrap_or_else(|| match options.get("format").map(|s| s.as_str()) {
+            Some("url") => "/",
+            _ => ".",
+        })
+}
+
+#[inline]
+fn apply_options(options: &HashMap<String, String>, field_path: String) -> String {
+    format_segments(options, converted_segments(options, &field_path))
+}
+
+/// Applies the options to the whole field path of a struct, prepending the `version` segment
+/// once before the other segments.
+#[inline]
+fn apply_versioned_options(options: &HashMap<String, String>, field_path: String) -> String {
+    let mut segments = converted_segments(options, &field_path);
+    if let Some(version) = options.get("version") {
+        segments.insert(0, version.clone());
+    }
+    format_segments(options, segments)
+}
+
+/// Joins the converted segments with the format or the delimiter.
+fn format_segments(options: &HashMap<String, String>, segments: Vec<String>) -> String {
+    let delim = delim_option(options);
+    if let Some(format_value) = options.get("format") {
+        match format_value.as_str() {
+            "url" => segments
+                .iter()
+                .map(|field_name| percent_encode(field_name))
+                .collect::<Vec<String>>()
+                .join(delim),
+            "json-schema" => format!(
+                "#/properties/{}",
+                segments
+                    .iter()
+                    .map(|field_name| json_pointer_escape(field_name))
+                    .collect::<Vec<String>>()
+                    .join("/properties/")
+            ),
+            "jsonpointer" => segments
+                .iter()
+                .map(|field_name| format!("/{}", json_pointer_escape(field_name)))
+                .collect::<String>(),
+            "graphql" => SelectionSet::from_paths(vec![segments]).to_string(),
+            another => panic!("Unknown format is specified: {}", another),
+        }
+    } else {
+        join_segments(options, delim, &segments)
+    }
+}
+
+/// Joins the segments with the delimiter, or only the first two of them when `flatten_delim` is specified
+/// joining all the deeper ones, so `delim = "/", flatten_delim = "."` produces `a/b.c`.
+fn join_segments(options: &HashMap<String, String>, delim: &str, segments: &[String]) -> String {
+    match (options.get("flatten_delim"), segments.split_first()) {
+        (Some(flatten_delim), Some((first_segment, nested_segments)))
+            if !nested_segments.is_empty() =>
+        {
+            format!(
+                "{}{}{}",
+                first_segment,
+                delim,
+                nested_segments.join(flatten_delim)
+            )
+        }
+        _ => segments.join(delim),
+    }
+}
+
+/// Converts the field path segments with the options before joining them with the format or the delimiter.
+fn converted_segments(options: &HashMap<String, String>, field_path: &str) -> Vec<String> {
+    try_converted_segments(options, field_path)
+        .unwrap_or_else(|error| panic!("{}", error.message()))
+}
+
+/// Parses the path as `try_parse_path` does and verifies its segments with `check_segments`,
+/// so the macros report the invalid input at the tokens instead of panicking.
+fn parse_checked_path(struct_path_stream: TokenStream) -> Result<ParsedPath, PathError> {
+    let parsed_path = try_parse_path(struct_path_stream)?;
+    check_segments(
+        &parsed_path.found_structs,
+        &parsed_path.field_spans,
+        &parsed_path.options,
+    )?;
+    Ok(parsed_path)
+}
+
+/// Parses the paths as `try_parse_paths` does and verifies their segments with `check_segments`.
+fn parse_checked_paths(struct_path_stream: TokenStream) -> Result<ParsedPaths, PathError> {
+    let parsed_paths = try_parse_paths(struct_path_stream)?;
+    check_segments(
+        &parsed_paths.found_structs,
+        &parsed_paths.field_spans,
+        &parsed_paths.options,
+    )?;
+    Ok(parsed_paths)
+}
+
+/// Verifies the segments of all the struct field paths with the options such as `segment_pattern`,
+/// `reserved` and `on_empty_segment`, reporting the violations at the segment tokens instead of
+/// panicking in the conversions.
+fn check_segments(
+    found_structs: &[(String, Vec<String>)],
+    field_spans: &[Vec<FieldSpans>],
+    options: &HashMap<String, String>,
+) -> Result<(), PathError> {
+    for (struct_pos, (_, struct_fields)) in found_structs.iter().enumerate() {
+        for (field_pos, field_path) in struct_fields.iter().enumerate() {
+            if let Err(error) = try_converted_segments(options, field_path) {
+                let path_spans = field_spans
+                    .get(struct_pos)
+                    .and_then(|struct_field_spans| struct_field_spans.get(field_pos))
+                    .map(|path_spans| path_spans.as_slice())
+                    .unwrap_or_default();
+                return Err(error.spanned_at(path_spans));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn try_converted_segments(
+    options: &HashMap<String, String>,
+    field_path: &str,
+) -> Result<Vec<String>, PathError> {
+    let segment_conversion = SegmentConversion::new(options);
+    let segment_pattern = options
+        .get("segment_pattern")
+        .map(|pattern| SegmentPattern::parse(pattern));
+    let on_empty_segment = match options.get("on_empty_segment").map(|s| s.as_str()) {
+        Some("error") | None => "error",
+        Some(policy @ ("skip" | "keep")) => policy,
+        Some(others) => panic!(
+            "Unknown on_empty_segment option value is specified: {}",
+            others
+        ),
+    };
+    let mut segments = Vec::with_capacity(16);
+    for (_, segment) in split_field_path(field_path) {
+        // The segment token of the macro input such as the field name for the errors
+        let (segment_token, output_segments) = if segment.starts_with('"') {
+            (segment, vec![string_literal_value(segment).unwrap()])
+        } else if let Some(map_key) = map_key_segment(segment) {
+            (segment, vec![map_key])
+        } else {
+            let (field_name, brackets) = split_segment_brackets(segment);
+            let (field_name, annotation) = split_segment_annotation(field_name);
+            let converted_field_name = match annotation {
+                Some("raw") => field_name.to_string(),
+                Some(another) => panic!("Unknown segment annotation is specified: @{}", another),
+                None => segment_conversion.convert(field_name, &segment_conversion.stages),
+            };
+            if brackets.contains(&WILDCARD_INDEX)
+                && brackets
+                    .iter()
+                    .any(|bracket| bracket.parse::<usize>().is_ok())
+            {
+                panic!(
+                    "The wildcard index can't be combined with the numeric indices: {}",
+                    segment
+                );
+            }
+            let mut output_segments = vec![converted_field_name];
+            for bracket in brackets {
+                match bracket_segment(bracket) {
+                    Some(bracket_segment) => output_segments.push(bracket_segment.to_string()),
+                    None => output_segments
+                        .last_mut()
+                        .unwrap()
+                        .push_str(&format!("[{}]", bracket)),
                 }
-            } else {
-                field_name.to_string()
             }
+            (field_name, output_segments)
+        };
+        for output_segment in output_segments {
+            if output_segment.is_empty() {
+                match on_empty_segment {
+                    "skip" => continue,
+                    "keep" => {}
+                    _ => {
+                        return Err(PathError::at_segment(
+                            segment_token,
+                            format!("Unexpected empty segment in path: {}", field_path),
+                        ))
+                    }
+                }
+            }
+            if let Some(segment_pattern) = &segment_pattern {
+                if !segment_pattern.matches(&output_segment) {
+                    return Err(PathError::at_segment(
+                        segment_token,
+                        format!(
+                            "Segment {} doesn't match the segment pattern: {}",
+                            output_segment, options["segment_pattern"]
+                        ),
+                    ));
+                }
+            }
+            if let Some(reserved) = options.get("reserved") {
+                if reserved
+                    .split(',')
+                    .any(|key| key.trim() == output_segment.as_str())
+                {
+                    return Err(PathError::at_segment(
+                        segment_token,
+                        format!("Segment {} is a reserved key: {}", output_segment, reserved),
+                    ));
+                }
+            }
+            segments.push(output_segment);
+        }
+    }
+    Ok(segments)
+}
+
+/// The stages of the segments conversion in their default order, reordered by the `pipeline` option.
+/// The `prefix` and `suffix` segments are converted by the stages following them in the pipeline.
+const PIPELINE_STAGES: [&str; 5] = ["abbrev", "case", "transform", "prefix", "suffix"];
+
+#[inline]
+fn pipeline_stages(options: &HashMap<String, String>) -> Vec<&'static str> {
+    let mut stages: Vec<&'static str> = Vec::with_capacity(PIPELINE_STAGES.len());
+    if let Some(pipeline) = options.get("pipeline") {
+        for stage_name in pipeline.split(',').map(|stage_name| stage_name.trim()) {
+            match PIPELINE_STAGES.iter().find(|stage| **stage == stage_name) {
+                Some(stage) if !stages.contains(stage) => stages.push(stage),
+                Some(_) => panic!("Duplicate pipeline stage is specified: {}", stage_name),
+                None => panic!("Unknown pipeline stage is specified: {}", stage_name),
+            }
+        }
+    }
+    for stage in PIPELINE_STAGES {
+        if !stages.contains(&stage) {
+            stages.push(stage);
+        }
+    }
+    stages
+}
+
+struct SegmentConversion<'a> {
+    stages: Vec<&'static str>,
+    abbreviations: Vec<(&'a str, &'a str)>,
+    case: Option<&'a str>,
+    digit_style: &'a str,
+    transform: Option<&'a str>,
+    preserve_leading_underscore: bool,
+    strip_trailing_underscore: bool,
+    upper_acronym_segments: bool,
+}
+
+/// The whole segments kept uppercase with `acronym_segments = "upper"`, such as `ID` for the `id` fields.
+const ACRONYM_SEGMENTS: [&str; 12] = [
+    "api", "css", "html", "http", "id", "ip", "json", "sql", "uri", "url", "uuid", "xml",
+];
+
+impl<'a> SegmentConversion<'a> {
+    fn new(options: &'a HashMap<String, String>) -> Self {
+        let preserve_leading_underscore = match options
+            .get("preserve_leading_underscore")
+            .map(|s| s.as_str())
+        {
+            Some("true") => true,
+            Some("false") | None => false,
+            Some(others) => panic!(
+                "Unknown preserve_leading_underscore option value is specified: {}",
+                others
+            ),
+        };
+        let strip_trailing_underscore =
+            match options.get("strip_trailing_underscore").map(|s| s.as_str()) {
+                Some("true") => true,
+                Some("false") | None => false,
+                Some(others) => panic!(
+                    "Unknown strip_trailing_underscore option value is specified: {}",
+                    others
+                ),
+            };
+        let upper_acronym_segments = match options.get("acronym_segments").map(|s| s.as_str()) {
+            Some("upper") => true,
+            Some("keep") | None => false,
+            Some(others) => panic!(
+                "Unknown acronym_segments option value is specified: {}",
+                others
+            ),
+        };
+        SegmentConversion {
+            stages: pipeline_stages(options),
+            abbreviations: options
+                .get("abbrev")
+                .map(|abbrev_value| parse_abbreviations(abbrev_value))
+                .unwrap_or_default(),
+            case: options.get("case").map(|s| s.as_str()),
+            digit_style: options
+                .get("digit_style")
+                .map(|s| s.as_str())
+                .unwrap_or("glued"),
+            transform: options.get("transform").map(|s| s.as_str()),
+            preserve_leading_underscore,
+            strip_trailing_underscore,
+            upper_acronym_segments,
+        }
+    }
+
+    /// Converts the `prefix` or `suffix` segment with the stages following it in the pipeline.
+    fn convert_affix(&self, affix_stage: &str, affix: &str) -> String {
+        let stage_pos = self
+            .stages
+            .iter()
+            .position(|stage| *stage == affix_stage)
+            .unwrap();
+        self.convert(affix, &self.stages[stage_pos + 1..])
+    }
+
+    fn convert(&self, field_name: &str, stages: &[&str]) -> String {
+        stages
+            .iter()
+            .fold(field_name.to_string(), |field_name, stage| match *stage {
+                "abbrev" => self
+                    .abbreviations
+                    .iter()
+                    .find(|(source, _)| *source == field_name)
+                    .map(|(_, abbreviation)| abbreviation.to_string())
+                    .unwrap_or(field_name),
+                "case" => {
+                    let field_name = match field_name.strip_suffix('_') {
+                        Some(stripped_field_name) if self.strip_trailing_underscore => {
+                            stripped_field_name
+                        }
+                        _ => &field_name,
+                    };
+                    let field_name = match self.case {
+                        Some(case_value) => self.convert_case(field_name, case_value),
+                        None => field_name.to_string(),
+                    };
+                    if self.upper_acronym_segments
+                        && ACRONYM_SEGMENTS.contains(&field_name.to_lowercase().as_str())
+                    {
+                        field_name.to_uppercase()
+                    } else {
+                        field_name
+                    }
+                }
+                "transform" => match self.transform {
+                    Some("lower-raw") => field_name.to_lowercase(),
+                    Some(another) => panic!("Unknown transform is specified: {}", another),
+                    None => field_name,
+                },
+                _ => field_name,
+            })
+    }
+
+    fn convert_case(&self, field_name: &str, case_value: &str) -> String {
+        let leading_underscores = if self.preserve_leading_underscore {
+            &field_name[..field_name.len() - field_name.trim_start_matches('_').len()]
+        } else {
+            ""
+        };
+        let field_name = match self.digit_style {
+            "glued" => convert_field_case(field_name, case_value),
+            "separated" => split_before_digits(field_name)
+                .into_iter()
+                .map(|field_name_part| convert_field_case(field_name_part, case_value))
+                .collect::<Vec<String>>()
+                .join("_"),
+            another => panic!("Unknown digit style is specified: {}", another),
+        };
+        format!("{}{}", leading_underscores, field_name)
+    }
+}
+
+#[inline]
+fn parse_abbreviations(abbrev_value: &str) -> Vec<(&str, &str)> {
+    abbrev_value
+        .split(',')
+        .map(|abbreviation| match abbreviation.split_once('=') {
+            Some((source, abbreviation)) if !source.trim().is_empty() => {
+                (source.trim(), abbreviation.trim())
+            }
+            _ => panic!("Unexpected abbreviation format: {}", abbreviation),
         })
-        .collect::<Vec<String>>()
-        .join(delim)
+        .collect()
+}
+
+#[inline]
+fn convert_field_case(field_name: &str, case_value: &str) -> String {
+    match case_value {
+        // The `case = Case::Camel` values are the tokens text, not the `convert_case::Case` values
+        "Case::Camel" => convert_field_case(field_name, "camel"),
+        "Case::Pascal" => convert_field_case(field_name, "pascal"),
+        "Case::Kebab" => convert_field_case(field_name, "kebab"),
+        "Case::UpperSnake" => convert_field_case(field_name, "upper_snake"),
+        "Case::Snake" => convert_field_case(field_name, "snake"),
+        "camel" => field_name.from_case(Case::Snake).to_case(Case::Camel),
+        "pascal" => field_name.from_case(Case::Snake).to_case(Case::Pascal),
+        "kebab" => field_name.from_case(Case::Snake).to_case(Case::Kebab),
+        "screaming" | "upper_snake" => field_name.from_case(Case::Snake).to_case(Case::UpperSnake),
+        // The only case that doesn't assume the snake case source to normalize the mixed case fields
+        "snake" => field_name.to_case(Case::Snake),
+        another => panic!("Unknown case is specified: {}", another),
+    }
+}
+
+#[inline]
+fn percent_encode(field_name: &str) -> String {
+    let mut encoded = String::with_capacity(field_name.len());
+    for byte in field_name.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[inline]
+fn json_pointer_escape(field_name: &str) -> String {
+    field_name.replace('~', "~0").replace('/', "~1")
+}
+
+#[inline]
+fn split_before_digits(field_name: &str) -> Vec<&str> {
+    let mut field_name_parts = Vec::new();
+    let mut part_start = 0;
+    for (pos, _) in field_name.match_indices('_') {
+        if field_name[pos + 1..].starts_with(|c: char| c.is_ascii_digit()) {
+            field_name_parts.push(&field_name[part_start..pos]);
+            part_start = pos + 1;
+        }
+    }
+    field_name_parts.push(&field_name[part_start..]);
+    field_name_parts
 }
@@ -0,0 +1,677 @@
+//! The parsers of the macros input on `proc_macro2`, keeping the spans of the fields
+//! for the diagnostics at the user's tokens.
+
+use crate::{
+    bracket_segment, error::PathError, simple_struct_name, split_field_path, WILDCARD_INDEX,
+};
+use proc_macro2::{Delimiter, Group, Literal, Punct, Span, TokenStream, TokenTree};
+use std::collections::HashMap;
+
+/// The fields and the literal segments of a field path with their spans in the macro input,
+/// so the diagnostics of the generated checks are reported at the user's tokens.
+pub type FieldSpans = Vec<(String, Span)>;
+
+pub struct ParsedPaths {
+    pub found_structs: Vec<(String, Vec<String>)>,
+    pub options: HashMap<String, String>,
+    pub field_spans: Vec<Vec<FieldSpans>>,
+}
+
+pub fn try_parse_paths(
+    struct_path_stream: proc_macro::TokenStream,
+) -> Result<ParsedPaths, PathError> {
+    parse_struct_paths(struct_path_stream, true)
+}
+
+/// The state machine shared by the `paths!` and `path!` inputs, where the `{...}` groups of
+/// the multiple fields of a struct are allowed only with `multiple_fields`.
+fn parse_struct_paths(
+    struct_path_stream: proc_macro::TokenStream,
+    multiple_fields: bool,
+) -> Result<ParsedPaths, PathError> {
+    let mut current_struct_name: Option<String> = None;
+    let mut current_struct_fields: Vec<String> = Vec::with_capacity(16);
+    let mut current_struct_field_spans: Vec<FieldSpans> = Vec::with_capacity(16);
+
+    let mut opened_struct = false;
+    let mut colons_counter = 0;
+    let mut options_opened = false;
+    let mut defaults_opened = false;
+    let mut current_path_option: Option<(String, String)> = None;
+
+    let mut current_field_path: Option<String> = None;
+
+    let mut current_option_name: Option<String> = None;
+    let mut expect_option_value: bool = false;
+
+    let mut options: HashMap<String, String> = HashMap::new();
+    let mut found_structs: Vec<(String, Vec<String>)> = Vec::new();
+    let mut field_spans: Vec<Vec<FieldSpans>> = Vec::new();
+    let mut current_field_spans: FieldSpans = Vec::new();
+    let mut struct_generic_args: Option<(usize, Vec<TokenTree>)> = None;
+    let mut enum_variant_opened = false;
+
+    for token_tree in TokenStream::from(struct_path_stream) {
+        if let Some(generic_args) = &mut struct_generic_args {
+            if let Some(turbofish) = push_generic_arg(generic_args, token_tree) {
+                struct_generic_args = None;
+                opened_struct = false;
+                colons_counter = 0;
+                if let Some(struct_name) = &mut current_struct_name {
+                    struct_name.push_str(&turbofish);
+                }
+            }
+            continue;
+        }
+        if enum_variant_opened
+            && !matches!(&token_tree, TokenTree::Ident(_) | TokenTree::Literal(_))
+            && !matches!(&token_tree, TokenTree::Punct(punct) if punct.as_char() == ':')
+        {
+            enum_variant_opened = false;
+            if let Some(field_path) = &mut current_field_path {
+                close_enum_variant(field_path, token_tree.span())?;
+            }
+        }
+        match token_tree {
+            TokenTree::Ident(id) if current_struct_name.is_none() => {
+                current_struct_name = Some(id.to_string());
+            }
+            TokenTree::Punct(punct)
+                if opened_struct && current_field_path.is_none() && punct.as_char() == '<' =>
+            {
+                struct_generic_args = Some((1, Vec::new()));
+            }
+            TokenTree::Punct(punct)
+                if current_struct_name.is_some()
+                    && !opened_struct
+                    && !options_opened
+                    && punct.as_char() == ':'
+                    && colons_counter < 2 =>
+            {
+                colons_counter += 1;
+                if colons_counter > 1 {
+                    opened_struct = true;
+                }
+            }
+            TokenTree::Ident(id) if opened_struct => {
+                colons_counter = 0;
+                current_field_spans.push((id.to_string(), id.span()));
+                if let Some(ref mut field_path) = &mut current_field_path {
+                    push_segment_name(field_path, &id.to_string())
+                } else {
+                    current_field_path = Some(id.to_string());
+                }
+            }
+            TokenTree::Literal(lit)
+                if opened_struct && (is_tuple_index(&lit) || is_literal_segment(&lit)) =>
+            {
+                colons_counter = 0;
+                current_field_spans.push((lit.to_string(), lit.span()));
+                if let Some(ref mut field_path) = &mut current_field_path {
+                    push_segment_name(field_path, &lit.to_string())
+                } else {
+                    current_field_path = Some(lit.to_string());
+                }
+            }
+            TokenTree::Group(group)
+                if opened_struct
+                    && group.delimiter() == Delimiter::Bracket
+                    && current_field_path.is_some() =>
+            {
+                if let Some(ref mut field_path) = &mut current_field_path {
+                    field_path.push_str(&parse_bracket_segment(&group)?);
+                }
+            }
+            TokenTree::Group(group)
+                if opened_struct
+                    && group.delimiter() == Delimiter::Brace
+                    && current_field_path.is_some() =>
+            {
+                if let Some(ref mut field_path) = &mut current_field_path {
+                    let map_values_segment = parse_map_values_segment(&group)?;
+                    current_field_spans.push((map_values_segment[1..].to_string(), group.span()));
+                    field_path.push_str(&map_values_segment);
+                }
+            }
+            TokenTree::Punct(punct)
+                if current_struct_name.is_some()
+                    && opened_struct
+                    && punct.as_char() == ':'
+                    && colons_counter < 2 =>
+            {
+                colons_counter += 1;
+                if enum_variant_opened
+                    || matches!((&current_struct_name, &current_field_path), (Some(struct_name), Some(field_path)) if opens_enum_variant(struct_name, field_path))
+                {
+                    enum_variant_opened = true;
+                    if let Some(field_path) = &mut current_field_path {
+                        field_path.push(':');
+                    }
+                } else {
+                    opened_struct = false;
+                    current_field_spans.clear();
+                    if let Some(ref mut field_path) = current_field_path.take() {
+                        if let Some(ref mut struct_name) = &mut current_struct_name {
+                            struct_name.push_str("::");
+                            struct_name.push_str(field_path);
+                        }
+                    }
+                }
+            }
+            TokenTree::Punct(punct)
+                if opened_struct
+                    && (punct.as_char() == '.'
+                        || punct.as_char() == '~'
+                        || punct.as_char() == '*'
+                        || punct.as_char() == '#'
+                        || punct.as_char() == '?'
+                        || punct.as_char() == '@') =>
+            {
+                if let Some(ref mut field_path) = &mut current_field_path {
+                    push_segment_punct(field_path, &punct)?;
+                } else {
+                    return Err(PathError::new(
+                        punct.span(),
+                        format!(
+                            "Unexpected punctuation input for struct path group parameters: {}",
+                            punct
+                        ),
+                    ));
+                }
+            }
+            TokenTree::Group(group)
+                if multiple_fields && opened_struct && current_field_path.is_none() =>
+            {
+                parse_multiple_fields(
+                    group.stream(),
+                    &mut current_struct_fields,
+                    &mut current_struct_field_spans,
+                )?
+            }
+            TokenTree::Punct(punct)
+                if !options_opened && opened_struct && punct.as_char() == ',' =>
+            {
+                opened_struct = false;
+                colons_counter = 0;
+                if let Some(struct_name) = current_struct_name.take() {
+                    if let Some(field_path) = current_field_path.take() {
+                        current_struct_fields.push(field_path);
+                        current_struct_field_spans.push(std::mem::take(&mut current_field_spans));
+                    }
+                    if !current_struct_fields.is_empty() {
+                        found_structs
+                            .push((struct_name, std::mem::take(&mut current_struct_fields)));
+                        field_spans.push(std::mem::take(&mut current_struct_field_spans));
+                    } else {
+                        return Err(PathError::new(
+                            punct.span(),
+                            format!("Unexpected comma with empty fields for {}!", struct_name),
+                        ));
+                    }
+                } else {
+                    return Err(PathError::new(
+                        punct.span(),
+                        "Unexpected comma with empty definitions!".to_string(),
+                    ));
+                }
+            }
+            TokenTree::Punct(punct)
+                if punct.as_char() == ';' && opened_struct && !options_opened =>
+            {
+                options_opened = true;
+                opened_struct = false;
+            }
+            TokenTree::Punct(punct) if punct.as_char() == ';' && options_opened => {
+                defaults_opened = true;
+                current_path_option = None;
+            }
+            TokenTree::Ident(id)
+                if options_opened
+                    && matches!(&current_path_option, Some((_, option_value)) if option_value.ends_with("::")) =>
+            {
+                if let Some((option_name, option_value)) = &mut current_path_option {
+                    option_value.push_str(&id.to_string());
+                    insert_option(
+                        &mut options,
+                        option_name.clone(),
+                        option_value.clone(),
+                        defaults_opened,
+                    );
+                }
+            }
+            TokenTree::Punct(punct)
+                if options_opened && punct.as_char() == ':' && current_path_option.is_some() =>
+            {
+                if let Some((_, option_value)) = &mut current_path_option {
+                    option_value.push(':');
+                }
+            }
+            TokenTree::Ident(id) if options_opened && !expect_option_value => {
+                current_option_name = Some(id.to_string())
+            }
+            TokenTree::Ident(id) if options_opened && expect_option_value => {
+                expect_option_value = false;
+                match current_option_name.take() {
+                    Some(option_name) => {
+                        current_path_option = Some((option_name.clone(), id.to_string()));
+                        insert_option(&mut options, option_name, id.to_string(), defaults_opened);
+                    }
+                    _ => {
+                        return Err(PathError::new(
+                            id.span(),
+                            "Wrong options format".to_string(),
+                        ));
+                    }
+                }
+            }
+            TokenTree::Literal(lit) if options_opened && expect_option_value => {
+                expect_option_value = false;
+                match current_option_name.take() {
+                    Some(option_name) => {
+                        let option_value = match string_literal_value(&lit.to_string()) {
+                            Some(option_value) => option_value,
+                            None => {
+                                return Err(PathError::new(
+                                    lit.span(),
+                                    format!("Expected a string literal option value: {}", lit),
+                                ))
+                            }
+                        };
+                        insert_option(&mut options, option_name, option_value, defaults_opened);
+                    }
+                    _ => {
+                        return Err(PathError::new(
+                            lit.span(),
+                            "Wrong options format".to_string(),
+                        ));
+                    }
+                }
+            }
+            TokenTree::Punct(punct) if options_opened && punct.as_char() == '=' => {
+                expect_option_value = true;
+            }
+            TokenTree::Punct(punct) if options_opened && punct.as_char() == ',' => {
+                expect_option_value = false;
+                current_path_option = None;
+            }
+            others => {
+                return Err(PathError::new(
+                    others.span(),
+                    format!("Unexpected input for struct path parameters: {}", others),
+                ));
+            }
+        }
+    }
+
+    if enum_variant_opened {
+        if let Some(field_path) = &mut current_field_path {
+            close_enum_variant(field_path, Span::call_site())?;
+        }
+    }
+
+    if let Some(field_path) = current_field_path.take() {
+        current_struct_fields.push(field_path);
+        current_struct_field_spans.push(std::mem::take(&mut current_field_spans));
+    }
+
+    if let Some(struct_name) = current_struct_name.take() {
+        if !current_struct_fields.is_empty() {
+            found_structs.push((struct_name, std::mem::take(&mut current_struct_fields)));
+            field_spans.push(std::mem::take(&mut current_struct_field_spans));
+        } else {
+            return Err(PathError::call_site(format!(
+                "Unexpected empty fields for {}!",
+                struct_name
+            )));
+        }
+    } else {
+        return Err(PathError::call_site(
+            "Unexpected empty definitions!".to_string(),
+        ));
+    }
+
+    Ok(ParsedPaths {
+        found_structs,
+        options,
+        field_spans,
+    })
+}
+
+/// The options after the next `;` are the defaults that don't override the explicit ones,
+/// so the macros generated by `define_path_macros!` can append them to any input.
+#[inline]
+fn insert_option(
+    options: &mut HashMap<String, String>,
+    option_name: String,
+    option_value: String,
+    default_option: bool,
+) {
+    if default_option {
+        options.entry(option_name).or_insert(option_value);
+    } else {
+        options.insert(option_name, option_value);
+    }
+}
+
+/// Parses the brackets following a field such as `items[1..3]`.
+/// Supported forms are the slice ranges with literal bounds: `[a..b]`, `[a..=b]`, `[a..]`, `[..b]`, `[..=b]` and `[..]`,
+/// and the enum variant map keys such as `[TestKind::Primary]`.
+fn parse_bracket_segment(bracket_group: &Group) -> Result<String, PathError> {
+    let bracket_stream = bracket_group.stream();
+    let bracket_content = bracket_stream
+        .into_iter()
+        .map(|token_tree| token_tree.to_string())
+        .collect::<String>();
+    let is_range_bound = |bound: &str| bound.chars().all(|c| c.is_ascii_digit());
+    let is_index = |index: &str| !index.is_empty() && is_range_bound(index);
+    let is_valid_range = match bracket_content.split_once("..") {
+        Some((start, end)) => match end.strip_prefix('=') {
+            Some(inclusive_end) => {
+                is_range_bound(start) && !inclusive_end.is_empty() && is_range_bound(inclusive_end)
+            }
+            None => is_range_bound(start) && is_range_bound(end),
+        },
+        None => {
+            is_index(&bracket_content)
+                || bracket_content == WILDCARD_INDEX
+                || bracket_segment(&bracket_content).is_some()
+        }
+    };
+    if !is_valid_range {
+        return Err(PathError::new(
+            bracket_group.span(),
+            format!(
+                "Unexpected brackets content for struct path: [{}]",
+                bracket_content
+            ),
+        ));
+    }
+    Ok(format!("[{}]", bracket_content))
+}
+
+/// Parses the map values placeholder following a field such as `fields{*}child_value_str`,
+/// with the `*` key segment or a string literal one such as `fields{"$key"}child_value_str`.
+fn parse_map_values_segment(braces_group: &Group) -> Result<String, PathError> {
+    let braces_tokens: Vec<TokenTree> = braces_group.stream().into_iter().collect();
+    match braces_tokens.as_slice() {
+        [TokenTree::Punct(punct)] if punct.as_char() == '*' => Ok(".{*}".to_string()),
+        [TokenTree::Literal(lit)] if is_literal_segment(lit) => Ok(format!(".{{{}}}", lit)),
+        _ => Err(PathError::new(
+            braces_group.span(),
+            format!(
+                "Unexpected map key segment for struct path, expected {{*}} or a string literal: {}",
+                braces_group
+            ),
+        )),
+    }
+}
+
+/// Pushes the field name or the literal segment, separating it from the preceding map values segment.
+#[inline]
+fn push_segment_name(field_path: &mut String, segment_name: &str) {
+    if field_path.ends_with('}') {
+        field_path.push('.');
+    }
+    field_path.push_str(segment_name)
+}
+
+/// Pushes the segments separators (including the `~`, `?`, `*` and `#` steps) and the `@` segment annotations such as `http_url@raw`,
+/// which are allowed only right after the field names.
+#[inline]
+fn push_segment_punct(field_path: &mut String, punct: &Punct) -> Result<(), PathError> {
+    if punct.as_char() == '@'
+        && !field_path
+            .chars()
+            .last()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_')
+    {
+        return Err(PathError::new(
+            punct.span(),
+            format!(
+                "Unexpected segment annotation after {}, annotations must follow the field names",
+                field_path
+            ),
+        ));
+    }
+    field_path.push(punct.as_char());
+    Ok(())
+}
+
+/// The string literal segments such as `"config"` in `value_child."config".child_value_str`.
+#[inline]
+fn is_literal_segment(lit: &Literal) -> bool {
+    lit.to_string().starts_with('"')
+}
+
+#[inline]
+fn is_tuple_index(lit: &Literal) -> bool {
+    let lit_str = lit.to_string();
+    lit_str
+        .split('.')
+        .all(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Collects the turbofish generic arguments of the struct such as `Container::<u64>`,
+/// returning the `::<...>` suffix of the struct name when the arguments are closed.
+fn push_generic_arg(
+    generic_args: &mut (usize, Vec<TokenTree>),
+    token_tree: TokenTree,
+) -> Option<String> {
+    let (angle_brackets_counter, generic_tokens) = generic_args;
+    if let TokenTree::Punct(punct) = &token_tree {
+        match punct.as_char() {
+            '<' => *angle_brackets_counter += 1,
+            '>' => *angle_brackets_counter -= 1,
+            _ => {}
+        }
+    }
+    if *angle_brackets_counter == 0 {
+        Some(format!(
+            "::<{}>",
+            generic_tokens.drain(..).collect::<TokenStream>()
+        ))
+    } else {
+        generic_tokens.push(token_tree);
+        None
+    }
+}
+
+/// The `::` after a field of a struct such as `Machine::state` opens the enum variant of the field
+/// `state::MachineState::Running::progress`, while after the other names it continues the module path of the struct.
+#[inline]
+fn opens_enum_variant(struct_name: &str, field_path: &str) -> bool {
+    let struct_last_name = simple_struct_name(struct_name);
+    let field_name = split_field_path(field_path).last().unwrap().1;
+    struct_last_name.starts_with(|c: char| c.is_uppercase())
+        && (field_name.starts_with(|c: char| c.is_lowercase() || c == '_')
+            || field_name.starts_with("r#"))
+}
+
+/// Closes the enum variant of the last segment such as `state::MachineState::Running::progress`,
+/// separating the variant field as `state::MachineState::Running.progress`.
+fn close_enum_variant(field_path: &mut String, span: Span) -> Result<(), PathError> {
+    let segment = split_field_path(field_path).last().unwrap().1;
+    if segment.matches("::").count() < 3 {
+        return Err(PathError::new(
+            span,
+            format!(
+                "Expected the enum field as field::Enum::Variant::variant_field: {}",
+                segment
+            ),
+        ));
+    }
+    let pos = field_path.rfind("::").unwrap();
+    field_path.replace_range(pos..pos + 2, ".");
+    Ok(())
+}
+
+#[inline]
+fn parse_multiple_fields(
+    group_stream: TokenStream,
+    found_struct_fields: &mut Vec<String>,
+    found_field_spans: &mut Vec<FieldSpans>,
+) -> Result<(), PathError> {
+    let mut current_field_path: Option<String> = None;
+    let mut current_field_spans: FieldSpans = Vec::new();
+
+    for token_tree in group_stream.into_iter() {
+        match token_tree {
+            TokenTree::Ident(id) => {
+                current_field_spans.push((id.to_string(), id.span()));
+                if let Some(ref mut field_path) = &mut current_field_path {
+                    push_segment_name(field_path, &id.to_string())
+                } else {
+                    current_field_path = Some(id.to_string());
+                }
+            }
+            TokenTree::Literal(lit) if is_tuple_index(&lit) || is_literal_segment(&lit) => {
+                current_field_spans.push((lit.to_string(), lit.span()));
+                if let Some(ref mut field_path) = &mut current_field_path {
+                    push_segment_name(field_path, &lit.to_string())
+                } else {
+                    current_field_path = Some(lit.to_string());
+                }
+            }
+            TokenTree::Group(group)
+                if group.delimiter() == Delimiter::Bracket && current_field_path.is_some() =>
+            {
+                if let Some(ref mut field_path) = &mut current_field_path {
+                    field_path.push_str(&parse_bracket_segment(&group)?);
+                }
+            }
+            TokenTree::Group(group)
+                if group.delimiter() == Delimiter::Brace && current_field_path.is_some() =>
+            {
+                if let Some(ref mut field_path) = &mut current_field_path {
+                    let map_values_segment = parse_map_values_segment(&group)?;
+                    current_field_spans.push((map_values_segment[1..].to_string(), group.span()));
+                    field_path.push_str(&map_values_segment);
+                }
+            }
+            TokenTree::Punct(punct) if punct.as_char() == ',' => {
+                if let Some(field_path) = current_field_path.take() {
+                    found_struct_fields.push(field_path);
+                    found_field_spans.push(std::mem::take(&mut current_field_spans));
+                } else {
+                    return Err(PathError::new(
+                        punct.span(),
+                        format!(
+                            "Unexpected punctuation input for struct path group parameters: {}",
+                            punct
+                        ),
+                    ));
+                }
+            }
+            TokenTree::Punct(punct)
+                if punct.as_char() == '.'
+                    || punct.as_char() == '~'
+                    || punct.as_char() == '*'
+                    || punct.as_char() == '#'
+                    || punct.as_char() == '?'
+                    || punct.as_char() == '@' =>
+            {
+                if let Some(ref mut field_path) = &mut current_field_path {
+                    push_segment_punct(field_path, &punct)?;
+                } else {
+                    return Err(PathError::new(
+                        punct.span(),
+                        format!(
+                            "Unexpected punctuation input for struct path group parameters: {}",
+                            punct
+                        ),
+                    ));
+                }
+            }
+            others => {
+                return Err(PathError::new(
+                    others.span(),
+                    format!(
+                        "Unexpected input for struct path group parameters: {}",
+                        others
+                    ),
+                ));
+            }
+        }
+    }
+
+    if let Some(field_path) = current_field_path.take() {
+        found_struct_fields.push(field_path);
+        found_field_spans.push(current_field_spans);
+    }
+    Ok(())
+}
+
+pub struct ParsedPath {
+    pub found_structs: Vec<(String, Vec<String>)>,
+    pub field_path: String,
+    pub options: HashMap<String, String>,
+    pub field_spans: Vec<Vec<FieldSpans>>,
+}
+
+pub fn try_parse_path(
+    struct_path_stream: proc_macro::TokenStream,
+) -> Result<ParsedPath, PathError> {
+    let ParsedPaths {
+        found_structs,
+        options,
+        field_spans,
+    } = parse_struct_paths(struct_path_stream, false)?;
+    let field_path = found_structs
+        .iter()
+        .flat_map(|(_, fields)| fields.iter().map(|field| field.as_str()))
+        .collect::<Vec<_>>()
+        .join(".");
+    Ok(ParsedPath {
+        found_structs,
+        field_path,
+        options,
+        field_spans,
+    })
+}
+
+/// Returns the value of a string literal such as `"a\"b"` or `r#"a"b"#` with the escapes resolved,
+/// or `None` for the other literals.
+pub fn string_literal_value(lit_str: &str) -> Option<String> {
+    if let Some(raw_lit_str) = lit_str.strip_prefix('r') {
+        let hashes = &raw_lit_str[..raw_lit_str.len() - raw_lit_str.trim_start_matches('#').len()];
+        return raw_lit_str
+            .strip_prefix(hashes)
+            .and_then(|raw_lit_str| raw_lit_str.strip_suffix(hashes))
+            .and_then(|raw_lit_str| raw_lit_str.strip_prefix('"'))
+            .and_then(|raw_lit_str| raw_lit_str.strip_suffix('"'))
+            .map(|raw_value| raw_value.to_string());
+    }
+
+    let escaped_value = lit_str.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::with_capacity(escaped_value.len());
+    let mut value_chars = escaped_value.chars().peekable();
+    while let Some(c) = value_chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match value_chars.next()? {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '0' => value.push('\0'),
+            '\\' => value.push('\\'),
+            '\'' => value.push('\''),
+            '"' => value.push('"'),
+            'x' => {
+                let code: String = value_chars.by_ref().take(2).collect();
+                value.push(char::from(u8::from_str_radix(&code, 16).ok()?));
+            }
+            'u' => {
+                value_chars.next_if_eq(&'{')?;
+                let code: String = value_chars.by_ref().take_while(|c| *c != '}').collect();
+                value.push(char::from_u32(
+                    u32::from_str_radix(&code.replace('_', ""), 16).ok()?,
+                )?);
+            }
+            '\n' => while value_chars.next_if(|c| c.is_whitespace()).is_some() {},
+            _ => return None,
+        }
+    }
+    Some(value)
+}
@@ -0,0 +1,128 @@
+//! A minimal pattern matcher for the `segment_pattern` option.
+//!
+//! Supported subset:
+//! - literal characters (`\` escapes the special ones);
+//! - `.` matching any character;
+//! - character classes such as `[a-z0-9_]` with ranges and `[^...]` negation;
+//! - `*`, `+` and `?` quantifiers for the preceding item.
+//!
+//! The pattern always matches the whole segment.
+
+enum PatternItem {
+    Any,
+    Char(char),
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl PatternItem {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            PatternItem::Any => true,
+            PatternItem::Char(pattern_char) => *pattern_char == c,
+            PatternItem::Class { negated, ranges } => {
+                ranges.iter().any(|(from, to)| *from <= c && c <= *to) != *negated
+            }
+        }
+    }
+}
+
+struct PatternToken {
+    item: PatternItem,
+    min: usize,
+    max: Option<usize>,
+}
+
+pub struct SegmentPattern {
+    tokens: Vec<PatternToken>,
+}
+
+impl SegmentPattern {
+    pub fn parse(pattern: &str) -> Self {
+        let mut tokens: Vec<PatternToken> = Vec::new();
+        let mut pattern_chars = pattern.chars().peekable();
+
+        while let Some(c) = pattern_chars.next() {
+            let item = match c {
+                '.' => PatternItem::Any,
+                '\\' => match pattern_chars.next() {
+                    Some(escaped) => PatternItem::Char(escaped),
+                    None => panic!("Unexpected end of segment pattern: {}", pattern),
+                },
+                '[' => {
+                    let negated = pattern_chars.next_if_eq(&'^').is_some();
+                    let mut ranges: Vec<(char, char)> = Vec::new();
+                    loop {
+                        match pattern_chars.next() {
+                            Some(']') if !ranges.is_empty() => break,
+                            Some('\\') => match pattern_chars.next() {
+                                Some(escaped) => ranges.push((escaped, escaped)),
+                                None => panic!("Unexpected end of segment pattern: {}", pattern),
+                            },
+                            Some(from) => {
+                                if pattern_chars.next_if_eq(&'-').is_some() {
+                                    match pattern_chars.next() {
+                                        Some(']') => {
+                                            ranges.push((from, from));
+                                            ranges.push(('-', '-'));
+                                            break;
+                                        }
+                                        Some(to) if from <= to => ranges.push((from, to)),
+                                        _ => panic!(
+                                            "Unexpected character range in segment pattern: {}",
+                                            pattern
+                                        ),
+                                    }
+                                } else {
+                                    ranges.push((from, from));
+                                }
+                            }
+                            None => {
+                                panic!("Unclosed character class in segment pattern: {}", pattern)
+                            }
+                        }
+                    }
+                    PatternItem::Class { negated, ranges }
+                }
+                '*' | '+' | '?' | ']' => {
+                    panic!("Unexpected '{}' in segment pattern: {}", c, pattern)
+                }
+                c => PatternItem::Char(c),
+            };
+
+            let (min, max) = match pattern_chars.next_if(|c| matches!(c, '*' | '+' | '?')) {
+                Some('*') => (0, None),
+                Some('+') => (1, None),
+                Some('?') => (0, Some(1)),
+                _ => (1, Some(1)),
+            };
+
+            tokens.push(PatternToken { item, min, max });
+        }
+
+        SegmentPattern { tokens }
+    }
+
+    pub fn matches(&self, segment: &str) -> bool {
+        let segment_chars: Vec<char> = segment.chars().collect();
+        matches_tokens(&self.tokens, &segment_chars)
+    }
+}
+
+fn matches_tokens(tokens: &[PatternToken], segment_chars: &[char]) -> bool {
+    match tokens.split_first() {
+        None => segment_chars.is_empty(),
+        Some((token, rest_tokens)) => {
+            let max_matched = segment_chars
+                .iter()
+                .take(token.max.unwrap_or(segment_chars.len()))
+                .take_while(|c| token.item.matches(**c))
+                .count();
+            (token.min..=max_matched)
+                .rev()
+                .any(|matched| matches_tokens(rest_tokens, &segment_chars[matched..]))
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! The GraphQL selection sets for the `graphql` format.
+//!
+//! The paths are merged into a prefix tree by their shared head segments,
+//! so `value_str` and `value_child.child_value_str` are `value_str value_child { child_value_str }`.
+//! The fields keep the order of their first occurrence.
+
+use std::fmt;
+
+struct SelectionField {
+    name: String,
+    selection_set: SelectionSet,
+}
+
+#[derive(Default)]
+pub struct SelectionSet {
+    fields: Vec<SelectionField>,
+}
+
+impl SelectionSet {
+    pub fn from_paths(paths: Vec<Vec<String>>) -> Self {
+        let mut selection_set = SelectionSet::default();
+        for path in paths {
+            selection_set.insert(path);
+        }
+        selection_set
+    }
+
+    fn insert(&mut self, path: Vec<String>) {
+        let mut path_segments = path.into_iter();
+        if let Some(name) = path_segments.next() {
+            let field_pos = match self.fields.iter().position(|field| field.name == name) {
+                Some(field_pos) => field_pos,
+                None => {
+                    self.fields.push(SelectionField {
+                        name,
+                        selection_set: SelectionSet::default(),
+                    });
+                    self.fields.len() - 1
+                }
+            };
+            self.fields[field_pos]
+                .selection_set
+                .insert(path_segments.collect());
+        }
+    }
+}
+
+impl fmt::Display for SelectionSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (field_pos, field) in self.fields.iter().enumerate() {
+            if field_pos > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(&field.name)?;
+            if !field.selection_set.fields.is_empty() {
+                write!(f, " {{ {} }}", field.selection_set)?;
+            }
+        }
+        Ok(())
+    }
+}